@@ -4,6 +4,7 @@ import {
   CreateMatchDto,
   JoinMatchDto,
   PlaceBetDto,
+  StartMatchDto,
   DeclareWinnerDto,
   WithdrawStakeDto,
   ClaimPayoutDto,
@@ -40,6 +41,11 @@ export class MatchController {
     return this.matchService.placeBet(dto);
   }
 
+  @Post("start")
+  start(@Body() dto: StartMatchDto) {
+    return this.matchService.startMatch(dto);
+  }
+
   @Post("declare-winner")
   declareWinner(@Body() dto: DeclareWinnerDto) {
     return this.matchService.declareWinner(dto);
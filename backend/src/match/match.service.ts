@@ -4,11 +4,15 @@ import * as anchor from "@coral-xyz/anchor";
 import { PublicKey } from "@solana/web3.js";
 
 export class CreateMatchDto {
-  id: number; // ex: 1
   stakeLamports: string; // string pra caber no BN
-  deadline: number; // unix timestamp (segundos)
+  fundBy: number; // unix timestamp (segundos) limite pros players depositarem
+  betsCloseAt: number; // unix timestamp (segundos) limite pras apostas
+  earliestResolution: number; // unix timestamp (segundos) a partir do qual sai o resultado
+  resolveBy: number; // unix timestamp (segundos) limite pra declarar o vencedor
   playerA: string; // pubkey base58
   playerB: string; // pubkey base58
+  unmatchedPoolPolicy?: "RefundBettors" | "WinningPlayer" | "Treasury"; // padrão: RefundBettors
+  arbiterFeeBps?: number; // taxa do árbitro em basis points (padrão: 0)
 }
 
 export class JoinMatchDto {
@@ -21,6 +25,10 @@ export class PlaceBetDto {
   amountLamports: string;
 }
 
+export class StartMatchDto {
+  matchPda: string;
+}
+
 export class DeclareWinnerDto {
   matchPda: string;
   winner: "PlayerA" | "PlayerB";
@@ -38,33 +46,98 @@ export class ClaimPayoutDto {
 export class MatchService {
   constructor(private readonly solana: SolanaService) {}
 
+  // PDA da configuração global do protocolo
+  private configPda(): PublicKey {
+    const [configPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      this.solana.programId,
+    );
+    return configPda;
+  }
+
+  // PDA do vault de lamports da partida (guarda o SOL do escrow)
+  private vaultPda(matchPda: PublicKey): PublicKey {
+    const [vaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), matchPda.toBuffer()],
+      this.solana.programId,
+    );
+    return vaultPda;
+  }
+
+  // Contas exigidas pelas instruções que emitem eventos via CPI (`emit_cpi!`)
+  private eventAccounts() {
+    const [eventAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("__event_authority")],
+      this.solana.programId,
+    );
+    return { eventAuthority, program: this.solana.programId };
+  }
+
   // POST /match/create
   async createMatch(dto: CreateMatchDto) {
     const program: any = this.solana.program;
 
+    const arbiter = this.solana.serverKeypair.publicKey;
+
+    // O id da partida vem do contador on-chain do árbitro
+    const [counterPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("match_counter"), arbiter.toBuffer()],
+      this.solana.programId,
+    );
+
+    if ((await this.solana.connection.getAccountInfo(counterPda)) === null) {
+      await program.methods
+        .initMatchCounter()
+        .accounts({
+          arbiter,
+          matchCounter: counterPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          ...this.eventAccounts(),
+        })
+        .rpc();
+    }
+
+    const counter = await program.account.matchCounter.fetch(counterPda);
+    const id: anchor.BN = counter.nextId;
+
     const [matchPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("match"), this.solana.serverKeypair.publicKey.toBuffer()],
+      [Buffer.from("match"), arbiter.toBuffer(), id.toArrayLike(Buffer, "le", 8)],
       this.solana.programId,
     );
 
     const txSig = await program.methods
-      .createMatch(
-        new anchor.BN(dto.id),
-        new anchor.BN(dto.stakeLamports),
-        new anchor.BN(dto.deadline),
-        new PublicKey(dto.playerA),
-        new PublicKey(dto.playerB),
-      )
+      .createMatch({
+        id,
+        stakeLamports: new anchor.BN(dto.stakeLamports),
+        fundBy: new anchor.BN(dto.fundBy),
+        betsCloseAt: new anchor.BN(dto.betsCloseAt),
+        earliestResolution: new anchor.BN(dto.earliestResolution),
+        resolveBy: new anchor.BN(dto.resolveBy),
+        playerA: new PublicKey(dto.playerA),
+        playerB: new PublicKey(dto.playerB),
+        unmatchedPoolPolicy:
+          dto.unmatchedPoolPolicy === "WinningPlayer"
+            ? { winningPlayer: {} }
+            : dto.unmatchedPoolPolicy === "Treasury"
+              ? { treasury: {} }
+              : { refundBettors: {} },
+        arbiterFeeBps: dto.arbiterFeeBps ?? 0,
+      })
       .accounts({
-        arbiter: this.solana.serverKeypair.publicKey,
+        arbiter,
+        config: this.configPda(),
+        matchCounter: counterPda,
         matchAccount: matchPda,
+        vault: this.vaultPda(matchPda),
         systemProgram: anchor.web3.SystemProgram.programId,
+        ...this.eventAccounts(),
       })
       .rpc();
 
     return {
       ok: true,
       txSig,
+      id: id.toString(),
       matchPda: matchPda.toBase58(),
     };
   }
@@ -79,8 +152,11 @@ export class MatchService {
       .accounts({
         // ⚠️ Aqui o jogador é o próprio serverKeypair
         player: this.solana.serverKeypair.publicKey,
+        config: this.configPda(),
         matchAccount: matchPubkey,
+        vault: this.vaultPda(matchPubkey),
         systemProgram: anchor.web3.SystemProgram.programId,
+        ...this.eventAccounts(),
       })
       .rpc();
 
@@ -108,9 +184,12 @@ export class MatchService {
       .placeBet(sideArg, new anchor.BN(dto.amountLamports))
       .accounts({
         bettor: this.solana.serverKeypair.publicKey,
+        config: this.configPda(),
         matchAccount: matchPubkey,
+        vault: this.vaultPda(matchPubkey),
         participant: participantPda,
         systemProgram: anchor.web3.SystemProgram.programId,
+        ...this.eventAccounts(),
       })
       .rpc();
 
@@ -121,6 +200,24 @@ export class MatchService {
     };
   }
 
+  // POST /match/start
+  async startMatch(dto: StartMatchDto) {
+    const program: any = this.solana.program;
+    const matchPubkey = new PublicKey(dto.matchPda);
+
+    // Fecha as apostas; só partidas iniciadas podem receber resultado
+    const txSig = await program.methods
+      .startMatch()
+      .accounts({
+        arbiter: this.solana.serverKeypair.publicKey,
+        matchAccount: matchPubkey,
+        ...this.eventAccounts(),
+      })
+      .rpc();
+
+    return { ok: true, txSig };
+  }
+
   // POST /match/declare-winner
   async declareWinner(dto: DeclareWinnerDto) {
     const program: any = this.solana.program;
@@ -129,11 +226,20 @@ export class MatchService {
     const winnerArg =
       dto.winner === "PlayerA" ? { playerA: {} } : { playerB: {} };
 
+    // A taxa do protocolo vai pra tesouraria definida na Config
+    const configPda = this.configPda();
+    const config = await program.account.config.fetch(configPda);
+
     const txSig = await program.methods
       .declareWinner(winnerArg)
       .accounts({
         arbiter: this.solana.serverKeypair.publicKey,
+        config: configPda,
+        treasury: config.treasury,
         matchAccount: matchPubkey,
+        vault: this.vaultPda(matchPubkey),
+        systemProgram: anchor.web3.SystemProgram.programId,
+        ...this.eventAccounts(),
       })
       .rpc();
 
@@ -149,7 +255,11 @@ export class MatchService {
       .withdrawWinnerStake()
       .accounts({
         winner: this.solana.serverKeypair.publicKey,
+        config: this.configPda(),
         matchAccount: matchPubkey,
+        vault: this.vaultPda(matchPubkey),
+        systemProgram: anchor.web3.SystemProgram.programId,
+        ...this.eventAccounts(),
       })
       .rpc();
 
@@ -174,8 +284,12 @@ export class MatchService {
       .claimBetPayout()
       .accounts({
         bettor: this.solana.serverKeypair.publicKey,
+        config: this.configPda(),
         matchAccount: matchPubkey,
+        vault: this.vaultPda(matchPubkey),
         participant: participantPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        ...this.eventAccounts(),
       })
       .rpc();
 
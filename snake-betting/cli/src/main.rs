@@ -0,0 +1,652 @@
+//! `snake-arbiter`: CLI do árbitro pro programa snake_betting.
+//!
+//! Fala direto com o RPC (sem passar pelo gateway REST do backend) e assina
+//! com o mesmo keypair do backend (`SERVER_KEYPAIR_PATH`). Pensado pra operação
+//! e plantão: criar partidas, inspecionar contas, declarar resultado, cancelar,
+//! suspender partidas (ou pausar o protocolo, com o keypair do admin) e limpar
+//! partidas encerradas.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::Discriminator;
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use snake_betting_client::{
+    accounts, instructions, pda, settlement, ClientError, Config, CreateMatchArgs, Match,
+    MatchStatus, Participant, Side, SplMint, UnmatchedPoolPolicy,
+};
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_rpc_client_api::config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_rpc_client_api::filter::{Memcmp, RpcFilterType};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signature, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+#[derive(Parser)]
+#[command(
+    name = "snake-arbiter",
+    version,
+    about = "CLI do árbitro do snake_betting"
+)]
+struct Cli {
+    /// Keypair do árbitro (JSON com os bytes da chave, igual ao do backend)
+    #[arg(long, short = 'k', env = "SERVER_KEYPAIR_PATH", global = true)]
+    keypair: Option<String>,
+
+    /// URL do RPC
+    #[arg(
+        long,
+        short = 'u',
+        env = "RPC_URL",
+        default_value = "https://api.devnet.solana.com",
+        global = true
+    )]
+    url: String,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Cria uma partida (cria o contador do árbitro se ainda não existir)
+    CreateMatch(CreateMatchOpts),
+
+    /// Lista as partidas de um árbitro (padrão: o dono do keypair)
+    List {
+        #[arg(long)]
+        arbiter: Option<Pubkey>,
+    },
+
+    /// Mostra uma conta Match
+    ShowMatch { match_account: Pubkey },
+
+    /// Mostra a participação de um apostador numa partida
+    ShowParticipant {
+        match_account: Pubkey,
+        bettor: Pubkey,
+    },
+
+    /// Inicia o jogo (fecha as apostas)
+    Start { match_account: Pubkey },
+
+    /// Declara o vencedor
+    DeclareWinner {
+        match_account: Pubkey,
+        #[arg(value_enum)]
+        winner: SideArg,
+    },
+
+    /// Declara empate
+    DeclareDraw { match_account: Pubkey },
+
+    /// Cancela a partida
+    Cancel { match_account: Pubkey },
+
+    /// Suspende depósitos e apostas numa partida (árbitro ou admin)
+    Suspend {
+        match_account: Pubkey,
+        /// Suspende também saques, reembolsos e varreduras
+        #[arg(long)]
+        withdrawals: bool,
+    },
+
+    /// Retoma uma partida suspensa, inclusive os saques
+    Resume { match_account: Pubkey },
+
+    /// Pausa o protocolo inteiro: partidas novas, depósitos e apostas (só o admin)
+    Pause {
+        /// Pausa também saques, reembolsos e varreduras
+        #[arg(long)]
+        withdrawals: bool,
+    },
+
+    /// Retoma o protocolo pausado, inclusive os saques (só o admin)
+    Unpause,
+
+    /// Expira partidas vencidas, devolve stakes/apostas reembolsáveis e fecha
+    /// participações e partidas liquidadas. Só mexe no que o programa já permite.
+    Sweep {
+        /// Só essa partida (padrão: todas as partidas do árbitro)
+        match_account: Option<Pubkey>,
+    },
+}
+
+#[derive(clap::Args)]
+struct CreateMatchOpts {
+    #[arg(long)]
+    player_a: Pubkey,
+
+    #[arg(long)]
+    player_b: Pubkey,
+
+    /// Stake de cada jogador (lamports, ou unidades do token em partidas SPL)
+    #[arg(long)]
+    stake: u64,
+
+    /// Apostas fecham daqui a N segundos
+    #[arg(long, default_value_t = 60)]
+    bets_close_in: i64,
+
+    /// Jogadores depositam até daqui a N segundos (padrão: quando as apostas fecham)
+    #[arg(long)]
+    fund_in: Option<i64>,
+
+    /// Resultado pode sair a partir de daqui a N segundos (padrão: quando as apostas fecham)
+    #[arg(long)]
+    earliest_resolution_in: Option<i64>,
+
+    /// Prazo pra declarar o resultado, daqui a N segundos
+    #[arg(long, default_value_t = 3600)]
+    resolve_in: i64,
+
+    /// O que fazer com o pool se ninguém apostar no vencedor
+    #[arg(long, value_enum, default_value_t = PolicyArg::RefundBettors)]
+    policy: PolicyArg,
+
+    /// Taxa do árbitro (em basis points)
+    #[arg(long, default_value_t = 0)]
+    arbiter_fee_bps: u16,
+
+    /// Mint da partida (sem isso a partida é em SOL)
+    #[arg(long)]
+    mint: Option<Pubkey>,
+
+    /// O mint é do Token-2022
+    #[arg(long, requires = "mint")]
+    token_2022: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum SideArg {
+    A,
+    B,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum PolicyArg {
+    RefundBettors,
+    WinningPlayer,
+    Treasury,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let rpc = RpcClient::new_with_commitment(cli.url.clone(), CommitmentConfig::confirmed());
+
+    match cli.command {
+        Command::CreateMatch(opts) => create_match(&rpc, &load_keypair(&cli.keypair)?, opts),
+        Command::List { arbiter } => {
+            let arbiter = match arbiter {
+                Some(arbiter) => arbiter,
+                None => load_keypair(&cli.keypair)?.pubkey(),
+            };
+            list_matches(&rpc, &arbiter)
+        }
+        Command::ShowMatch { match_account } => show_match(&rpc, &match_account),
+        Command::ShowParticipant {
+            match_account,
+            bettor,
+        } => show_participant(&rpc, &match_account, &bettor),
+        Command::Start { match_account } => {
+            let arbiter = load_keypair(&cli.keypair)?;
+            let ix = instructions::start_match(match_account, arbiter.pubkey());
+            send(&rpc, &arbiter, &[ix]).map(print_signature)
+        }
+        Command::DeclareWinner {
+            match_account,
+            winner,
+        } => declare_winner(&rpc, &load_keypair(&cli.keypair)?, match_account, winner),
+        Command::DeclareDraw { match_account } => {
+            let arbiter = load_keypair(&cli.keypair)?;
+            let ix = instructions::declare_draw(match_account, arbiter.pubkey());
+            send(&rpc, &arbiter, &[ix]).map(print_signature)
+        }
+        Command::Cancel { match_account } => {
+            let arbiter = load_keypair(&cli.keypair)?;
+            let ix = instructions::cancel_match(match_account, arbiter.pubkey());
+            send(&rpc, &arbiter, &[ix]).map(print_signature)
+        }
+        Command::Suspend {
+            match_account,
+            withdrawals,
+        } => {
+            let authority = load_keypair(&cli.keypair)?;
+            let ix = instructions::set_match_suspended(
+                match_account,
+                authority.pubkey(),
+                true,
+                withdrawals,
+            );
+            send(&rpc, &authority, &[ix]).map(print_signature)
+        }
+        Command::Resume { match_account } => {
+            let authority = load_keypair(&cli.keypair)?;
+            let ix =
+                instructions::set_match_suspended(match_account, authority.pubkey(), false, false);
+            send(&rpc, &authority, &[ix]).map(print_signature)
+        }
+        Command::Pause { withdrawals } => {
+            let admin = load_keypair(&cli.keypair)?;
+            let ix = instructions::set_paused(admin.pubkey(), true, withdrawals);
+            send(&rpc, &admin, &[ix]).map(print_signature)
+        }
+        Command::Unpause => {
+            let admin = load_keypair(&cli.keypair)?;
+            let ix = instructions::set_paused(admin.pubkey(), false, false);
+            send(&rpc, &admin, &[ix]).map(print_signature)
+        }
+        Command::Sweep { match_account } => {
+            let payer = load_keypair(&cli.keypair)?;
+            let matches = match match_account {
+                Some(key) => vec![(key, fetch_match(&rpc, &key)?)],
+                None => matches_by_arbiter(&rpc, &payer.pubkey())?,
+            };
+            for (key, m) in matches {
+                if let Err(err) = sweep_match(&rpc, &payer, &key, m) {
+                    eprintln!("{key}: {err:#}");
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Lê o keypair no mesmo formato do backend (array JSON de bytes)
+fn load_keypair(path: &Option<String>) -> Result<Keypair> {
+    let path = path
+        .as_deref()
+        .ok_or_else(|| anyhow!("keypair não informado (--keypair ou SERVER_KEYPAIR_PATH)"))?;
+    read_keypair_file(path).map_err(|err| anyhow!("falha ao ler o keypair {path}: {err}"))
+}
+
+/// Assina com `payer` e envia; erros do programa saem já traduzidos
+fn send(rpc: &RpcClient, payer: &Keypair, ixs: &[Instruction]) -> Result<Signature> {
+    let blockhash = rpc.get_latest_blockhash()?;
+    let tx = Transaction::new_signed_with_payer(ixs, Some(&payer.pubkey()), &[payer], blockhash);
+
+    rpc.send_and_confirm_transaction(&tx)
+        .map_err(|err| match err.get_transaction_error() {
+            Some(TransactionError::InstructionError(_, InstructionError::Custom(code))) => {
+                anyhow!(ClientError::from_code(code))
+            }
+            _ => anyhow!(err),
+        })
+}
+
+fn print_signature(signature: Signature) {
+    println!("{signature}");
+}
+
+fn now() -> Result<i64> {
+    Ok(SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64)
+}
+
+fn fetch_match(rpc: &RpcClient, key: &Pubkey) -> Result<Match> {
+    let data = rpc
+        .get_account_data(key)
+        .with_context(|| format!("partida {key} não encontrada"))?;
+    Ok(accounts::decode_match(&data)?)
+}
+
+fn fetch_config(rpc: &RpcClient) -> Result<Config> {
+    Ok(accounts::decode_config(
+        &rpc.get_account_data(&pda::config_address())?,
+    )?)
+}
+
+/// Mint da partida com o programa de tokens dono dele (None em partidas SOL)
+fn spl_of(rpc: &RpcClient, m: &Match) -> Result<Option<SplMint>> {
+    let Some(mint) = m.mint else {
+        return Ok(None);
+    };
+    let mint_account = rpc.get_account(&mint)?;
+    Ok(Some(SplMint {
+        mint,
+        token_program: mint_account.owner,
+    }))
+}
+
+/// Contas do programa com o discriminador `T` e `key` no offset 8
+/// (o primeiro campo: `arbiter` na Match, `match_pubkey` na Participant)
+fn program_accounts<T: Discriminator>(
+    rpc: &RpcClient,
+    key: &Pubkey,
+) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, T::DISCRIMINATOR)),
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(8, key.as_ref())),
+        ]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+
+    Ok(rpc
+        .get_program_accounts_with_config(&snake_betting_client::ID, config)?
+        .into_iter()
+        .map(|(key, account)| (key, account.data))
+        .collect())
+}
+
+fn matches_by_arbiter(rpc: &RpcClient, arbiter: &Pubkey) -> Result<Vec<(Pubkey, Match)>> {
+    let mut matches = program_accounts::<Match>(rpc, arbiter)?
+        .into_iter()
+        .map(|(key, data)| Ok((key, accounts::decode_match(&data)?)))
+        .collect::<Result<Vec<_>>>()?;
+    matches.sort_by_key(|(_, m)| m.id);
+    Ok(matches)
+}
+
+fn participants_of(rpc: &RpcClient, match_account: &Pubkey) -> Result<Vec<Participant>> {
+    program_accounts::<Participant>(rpc, match_account)?
+        .into_iter()
+        .map(|(_, data)| Ok(accounts::decode_participant(&data)?))
+        .collect()
+}
+
+fn create_match(rpc: &RpcClient, arbiter: &Keypair, opts: CreateMatchOpts) -> Result<()> {
+    let arbiter_key = arbiter.pubkey();
+    let mut ixs = Vec::new();
+
+    // O id vem do contador do árbitro; na primeira partida o contador é criado junto
+    let counter_key = pda::match_counter_address(&arbiter_key);
+    let id = match rpc.get_account_data(&counter_key) {
+        Ok(data) => accounts::decode_match_counter(&data)?.next_id,
+        Err(_) => {
+            ixs.push(instructions::init_match_counter(arbiter_key));
+            0
+        }
+    };
+
+    let now = now()?;
+    let bets_close_at = now + opts.bets_close_in;
+    let args = CreateMatchArgs {
+        id,
+        stake_lamports: opts.stake,
+        fund_by: now + opts.fund_in.unwrap_or(opts.bets_close_in),
+        bets_close_at,
+        earliest_resolution: opts
+            .earliest_resolution_in
+            .map_or(bets_close_at, |secs| now + secs),
+        resolve_by: now + opts.resolve_in,
+        player_a: opts.player_a,
+        player_b: opts.player_b,
+        unmatched_pool_policy: match opts.policy {
+            PolicyArg::RefundBettors => UnmatchedPoolPolicy::RefundBettors,
+            PolicyArg::WinningPlayer => UnmatchedPoolPolicy::WinningPlayer,
+            PolicyArg::Treasury => UnmatchedPoolPolicy::Treasury,
+        },
+        arbiter_fee_bps: opts.arbiter_fee_bps,
+    };
+    let spl = opts.mint.map(|mint| {
+        if opts.token_2022 {
+            SplMint::token_2022(mint)
+        } else {
+            SplMint::token(mint)
+        }
+    });
+
+    ixs.push(instructions::create_match(arbiter_key, args, spl));
+    let signature = send(rpc, arbiter, &ixs)?;
+
+    println!("match: {}", pda::match_address(&arbiter_key, id));
+    println!("id: {id}");
+    println!("tx: {signature}");
+    Ok(())
+}
+
+fn list_matches(rpc: &RpcClient, arbiter: &Pubkey) -> Result<()> {
+    for (key, m) in matches_by_arbiter(rpc, arbiter)? {
+        println!(
+            "{:>6}  {key}  {:<10}  stake_pot={} side_a={} side_b={} participants={}",
+            m.id,
+            format!("{:?}", m.status),
+            m.stake_pot,
+            m.total_side_a,
+            m.total_side_b,
+            m.open_participants,
+        );
+    }
+    Ok(())
+}
+
+fn show_match(rpc: &RpcClient, key: &Pubkey) -> Result<()> {
+    let m = fetch_match(rpc, key)?;
+    let config = fetch_config(rpc)?;
+
+    println!("match:               {key}");
+    println!("id:                  {}", m.id);
+    println!("arbiter:             {}", m.arbiter);
+    println!("status:              {:?}", m.status);
+    println!(
+        "suspended:           {} (withdrawals: {})",
+        m.suspended, m.withdrawals_suspended
+    );
+    println!("winner:              {:?}", m.winner);
+    println!(
+        "mint:                {}",
+        m.mint.map_or("SOL".to_string(), |mint| mint.to_string())
+    );
+    println!(
+        "player_a:            {} (deposited: {}, stake: {})",
+        m.player_a, m.player_a_deposited, m.player_a_stake
+    );
+    println!(
+        "player_b:            {} (deposited: {}, stake: {})",
+        m.player_b, m.player_b_deposited, m.player_b_stake
+    );
+    println!("stake_lamports:      {}", m.stake_lamports);
+    println!("stake_pot:           {}", m.stake_pot);
+    println!("stakes_withdrawn:    {}", m.stakes_withdrawn);
+    println!("total_side_a:        {}", m.total_side_a);
+    println!("total_side_b:        {}", m.total_side_b);
+    println!("payout_pool:         {}", m.payout_pool);
+    println!(
+        "fees (bps):          protocol={} arbiter={}",
+        m.protocol_fee_bps, m.arbiter_fee_bps
+    );
+    println!(
+        "fees charged:        protocol={} arbiter={}",
+        m.protocol_fee, m.arbiter_fee
+    );
+    println!(
+        "unmatched policy:    {:?} (claimed: {})",
+        m.unmatched_pool_policy, m.unmatched_pool_claimed
+    );
+    println!("open_participants:   {}", m.open_participants);
+    println!(
+        "bettors:             side_a={} side_b={} (payouts claimed: {})",
+        m.participants_side_a, m.participants_side_b, m.payouts_claimed
+    );
+    println!("dust_swept:          {}", m.dust_swept);
+    println!(
+        "claim_deadline:      {} (window: {}s, unclaimed swept: {})",
+        m.claim_deadline_at(&config, now()?),
+        m.claim_window,
+        m.unclaimed_swept
+    );
+    println!("escrowed:            {}", m.escrowed);
+    println!("fund_by:             {}", m.fund_by);
+    println!("bets_close_at:       {}", m.bets_close_at);
+    println!("earliest_resolution: {}", m.earliest_resolution);
+    println!("resolve_by:          {}", m.resolve_by);
+    println!("started_at:          {}", m.started_at);
+    println!("vault:               {}", pda::vault_address(key));
+    if m.mint.is_some() {
+        println!("token_vault:         {}", pda::token_vault_address(key));
+    }
+    Ok(())
+}
+
+fn show_participant(rpc: &RpcClient, match_account: &Pubkey, bettor: &Pubkey) -> Result<()> {
+    let key = pda::participant_address(match_account, bettor);
+    let data = rpc
+        .get_account_data(&key)
+        .with_context(|| format!("participação {key} não encontrada"))?;
+    let p = accounts::decode_participant(&data)?;
+
+    println!("participant: {key}");
+    println!("match:       {}", p.match_pubkey);
+    println!("bettor:      {}", p.bettor);
+    println!("side:        {:?}", p.side);
+    println!("amount:      {}", p.amount);
+    println!("claimed:     {}", p.claimed);
+
+    // Prêmio de quem apostou no vencedor, com a mesma conta do programa
+    let m = fetch_match(rpc, &p.match_pubkey)?;
+    if m.status == MatchStatus::Resolved && m.winner == Some(p.side) {
+        let payout = settlement::bet_payout(p.amount, m.payout_pool, m.winner_bets_total())?;
+        println!("payout:      {payout}");
+        let config = fetch_config(rpc)?;
+        println!("claim until: {}", m.claim_deadline_at(&config, now()?));
+    }
+    Ok(())
+}
+
+fn declare_winner(
+    rpc: &RpcClient,
+    arbiter: &Keypair,
+    match_account: Pubkey,
+    winner: SideArg,
+) -> Result<()> {
+    let m = fetch_match(rpc, &match_account)?;
+    let config = fetch_config(rpc)?;
+    let winner = match winner {
+        SideArg::A => Side::PlayerA,
+        SideArg::B => Side::PlayerB,
+    };
+
+    let ix = instructions::declare_winner(
+        match_account,
+        arbiter.pubkey(),
+        config.treasury,
+        winner,
+        spl_of(rpc, &m)?,
+    );
+    send(rpc, arbiter, &[ix]).map(print_signature)
+}
+
+/// Leva uma partida o mais longe possível rumo ao fechamento, sem decidir nada
+/// pelo árbitro: expira se o prazo passou, devolve o que é reembolsável, manda o
+/// pool sem apostas no vencedor pro destino da política, varre a sobra de
+/// arredondamento e fecha o que já está liquidado. Prêmios não sacados
+/// continuam com os vencedores até `claim_deadline`; depois vão pra tesouraria.
+fn sweep_match(rpc: &RpcClient, payer: &Keypair, key: &Pubkey, mut m: Match) -> Result<()> {
+    let spl = spl_of(rpc, &m)?;
+    let config = fetch_config(rpc)?;
+
+    let open = matches!(
+        m.status,
+        MatchStatus::Created | MatchStatus::Funded | MatchStatus::InProgress
+    );
+    let now = now()?;
+    let expired = (m.status == MatchStatus::Created && now >= m.fund_by) || now >= m.resolve_by;
+    if open && expired {
+        send(rpc, payer, &[instructions::expire_match(*key)])?;
+        println!("{key}: expired");
+        m = fetch_match(rpc, key)?;
+    }
+
+    if m.stakes_refundable() {
+        for (player, deposited) in [
+            (m.player_a, m.player_a_deposited),
+            (m.player_b, m.player_b_deposited),
+        ] {
+            if deposited {
+                send(
+                    rpc,
+                    payer,
+                    &[instructions::refund_player(*key, player, spl)],
+                )?;
+                println!("{key}: refunded player {player}");
+            }
+        }
+    }
+
+    if m.status == MatchStatus::Resolved && !m.claim_window_open(&config, now) && !m.unclaimed_swept {
+        send(
+            rpc,
+            payer,
+            &[instructions::sweep_unclaimed(
+                *key,
+                payer.pubkey(),
+                config.treasury,
+                spl,
+            )],
+        )?;
+        println!("{key}: swept unclaimed funds to {}", config.treasury);
+        m = fetch_match(rpc, key)?;
+    }
+
+    let refund_unmatched = m.pool_unmatched()
+        && m.unmatched_pool_policy == UnmatchedPoolPolicy::RefundBettors
+        && m.claim_window_open(&config, now);
+    for mut p in participants_of(rpc, key)? {
+        if !p.claimed && (m.bets_refundable() || refund_unmatched) {
+            send(rpc, payer, &[instructions::refund_bet(*key, p.bettor, spl)])?;
+            println!("{key}: refunded bettor {}", p.bettor);
+            p.claimed = true;
+        }
+        if p.is_settled(&m) {
+            send(
+                rpc,
+                payer,
+                &[instructions::close_participant(*key, p.bettor)],
+            )?;
+            println!("{key}: closed participant of {}", p.bettor);
+        }
+    }
+
+    let mut m = fetch_match(rpc, key)?;
+
+    // Sem apostas no vencedor, o pool vai pro destino da política (jogador
+    // vencedor ou tesouraria); a partida só fecha depois disso
+    let pool_claimable = m.pool_unmatched()
+        && m.unmatched_pool_policy != UnmatchedPoolPolicy::RefundBettors
+        && !m.unmatched_pool_claimed
+        && !m.unclaimed_swept
+        && m.claim_window_open(&config, now);
+    if pool_claimable {
+        let recipient = m.unmatched_pool_recipient(config.treasury)?;
+        send(
+            rpc,
+            payer,
+            &[instructions::claim_unmatched_pool(*key, recipient, spl)],
+        )?;
+        println!("{key}: sent unmatched pool to {recipient}");
+        m = fetch_match(rpc, key)?;
+    }
+
+    let dust_sweepable = m.status == MatchStatus::Resolved
+        && m.winner_bets_total() > 0
+        && m.payouts_claimed == m.winner_participants()
+        && !m.dust_swept
+        && !m.unclaimed_swept;
+    if dust_sweepable {
+        let recipient = m.dust_recipient(config.treasury)?;
+        send(rpc, payer, &[instructions::sweep_dust(*key, recipient, spl)])?;
+        println!("{key}: swept dust to {recipient}");
+        m = fetch_match(rpc, key)?;
+    }
+
+    if m.is_settled() {
+        send(
+            rpc,
+            payer,
+            &[instructions::close_match(*key, m.arbiter, spl)],
+        )?;
+        println!("{key}: closed");
+    } else if !open || expired {
+        bail!("ainda não liquidada (status {:?})", m.status);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+//! Desserialização das contas do programa a partir dos dados brutos (RPC).
+
+use anchor_lang::AccountDeserialize;
+use snake_betting::{Config, Match, MatchCounter, Participant};
+
+use crate::error::ClientError;
+
+/// Desserializa qualquer conta do programa (confere o discriminador do Anchor).
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> Result<T, ClientError> {
+    let mut data = data;
+    T::try_deserialize(&mut data).map_err(ClientError::Deserialize)
+}
+
+/// Desserializa uma conta `Match`
+pub fn decode_match(data: &[u8]) -> Result<Match, ClientError> {
+    decode(data)
+}
+
+/// Desserializa uma conta `Participant`
+pub fn decode_participant(data: &[u8]) -> Result<Participant, ClientError> {
+    decode(data)
+}
+
+/// Desserializa a conta `Config`
+pub fn decode_config(data: &[u8]) -> Result<Config, ClientError> {
+    decode(data)
+}
+
+/// Desserializa uma conta `MatchCounter`
+pub fn decode_match_counter(data: &[u8]) -> Result<MatchCounter, ClientError> {
+    decode(data)
+}
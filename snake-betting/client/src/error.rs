@@ -0,0 +1,137 @@
+//! Tradução dos códigos de erro do programa pra tipos Rust.
+
+use std::fmt;
+
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use snake_betting::CustomError;
+
+/// Monta `PROGRAM_ERRORS` a partir da lista de variantes.
+///
+/// Junto vai um `match` exaustivo sobre `CustomError` (nunca executado): se o
+/// programa ganhar um erro que ficou fora da lista, o cliente não compila.
+macro_rules! program_errors {
+    ($($variant:ident),* $(,)?) => {
+        /// Todos os erros do programa, na ordem em que são declarados em `CustomError`
+        /// (o código de cada um é `ERROR_CODE_OFFSET + posição`; a ordem é
+        /// conferida em `tests/error.rs`).
+        const PROGRAM_ERRORS: &[CustomError] = &[$(CustomError::$variant),*];
+
+        const _: fn(CustomError) = |err| match err {
+            $(CustomError::$variant => {})*
+        };
+    };
+}
+
+program_errors![
+    InvalidDeadline,
+    InvalidStake,
+    InvalidStatus,
+    NotAPlayer,
+    AlreadyDeposited,
+    InvalidAmount,
+    BetsClosed,
+    SideMismatch,
+    MathOverflow,
+    NotArbiter,
+    TooEarly,
+    AlreadyResolved,
+    NoWinner,
+    NotWinnerPlayer,
+    StakesAlreadyWithdrawn,
+    NotBettor,
+    WrongSide,
+    AlreadyClaimed,
+    NoBetsOnWinnerSide,
+    MatchIdMismatch,
+    NothingToRefund,
+    InvalidResolveBy,
+    ResolutionExpired,
+    ResolutionNotExpired,
+    MatchNotFunded,
+    WalkoverNotAllowed,
+    InvalidFundBy,
+    FundingClosed,
+    FundingNotClosed,
+    ParticipantNotSettled,
+    MatchNotSettled,
+    PoolNotUnmatched,
+    WrongUnmatchedPoolPolicy,
+    NotAdmin,
+    InvalidConfig,
+    StakeBelowMinimum,
+    BetBelowMinimum,
+    MatchTooLong,
+    WrongRecipient,
+    FeeTooHigh,
+    MissingTokenAccounts,
+    InvalidTokenAccount,
+    EscrowInvariantViolated,
+    MatchNotStarted,
+    InvalidEarliestResolution,
+    PayoutsPending,
+    ClaimWindowExpired,
+    ClaimWindowOpen,
+    NotMatchAuthority,
+    ProtocolPaused,
+    MatchSuspended,
+    WithdrawalsPaused,
+    MatchWithdrawalsSuspended,
+];
+
+/// Erro devolvido pelo cliente
+#[derive(Debug)]
+pub enum ClientError {
+    /// Erro do próprio programa (`CustomError`)
+    Program(CustomError),
+
+    /// Erro do framework Anchor (códigos abaixo de `ERROR_CODE_OFFSET`)
+    Anchor(u32),
+
+    /// Código customizado que este cliente não conhece (programa mais novo?)
+    Unknown(u32),
+
+    /// Conta não pôde ser desserializada (tipo errado ou dados corrompidos)
+    Deserialize(anchor_lang::error::Error),
+}
+
+impl ClientError {
+    /// Converte o código de erro de uma transação (o `u32` de
+    /// `InstructionError::Custom`) no erro correspondente.
+    pub fn from_code(code: u32) -> Self {
+        if code < ERROR_CODE_OFFSET {
+            return ClientError::Anchor(code);
+        }
+
+        match program_error(code) {
+            Some(err) => ClientError::Program(err),
+            None => ClientError::Unknown(code),
+        }
+    }
+}
+
+/// Erro do programa com esse código, se existir
+pub fn program_error(code: u32) -> Option<CustomError> {
+    let index = code.checked_sub(ERROR_CODE_OFFSET)?;
+    PROGRAM_ERRORS.get(index as usize).copied()
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Program(err) => {
+                write!(f, "snake_betting error {}: {}", u32::from(*err), err)
+            }
+            ClientError::Anchor(code) => write!(f, "anchor error {code}"),
+            ClientError::Unknown(code) => write!(f, "unknown custom error {code}"),
+            ClientError::Deserialize(err) => write!(f, "failed to deserialize account: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<CustomError> for ClientError {
+    fn from(err: CustomError) -> Self {
+        ClientError::Program(err)
+    }
+}
@@ -0,0 +1,556 @@
+//! Construtores tipados das instruções do programa.
+//!
+//! Cada função monta a `Instruction` completa (program id, contas e dados),
+//! derivando as PDAs necessárias. Quem chama só precisa assinar e enviar.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use snake_betting::{accounts, instruction, ConfigArgs, CreateMatchArgs, Side};
+
+use crate::pda;
+
+/// Mint de uma partida SPL e o programa de tokens dono dele.
+///
+/// As contas de token dos usuários são as associated token accounts (ATA)
+/// de cada wallet pra esse mint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SplMint {
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+}
+
+impl SplMint {
+    /// Mint do SPL Token clássico
+    pub fn token(mint: Pubkey) -> Self {
+        SplMint {
+            mint,
+            token_program: anchor_spl::token::ID,
+        }
+    }
+
+    /// Mint do Token-2022
+    pub fn token_2022(mint: Pubkey) -> Self {
+        SplMint {
+            mint,
+            token_program: anchor_spl::token_2022::ID,
+        }
+    }
+
+    /// ATA de `owner` pra esse mint
+    pub fn token_account(&self, owner: &Pubkey) -> Pubkey {
+        get_associated_token_address_with_program_id(owner, &self.mint, &self.token_program)
+    }
+}
+
+/// Contas de token comuns a toda movimentação do escrow: (mint, token_vault, token_program).
+/// Em partidas em SOL (`spl = None`) vão todas como `None`.
+fn spl_accounts(
+    match_pubkey: &Pubkey,
+    spl: Option<SplMint>,
+) -> (Option<Pubkey>, Option<Pubkey>, Option<Pubkey>) {
+    match spl {
+        Some(spl) => (
+            Some(spl.mint),
+            Some(pda::token_vault_address(match_pubkey)),
+            Some(spl.token_program),
+        ),
+        None => (None, None, None),
+    }
+}
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: snake_betting::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// `initialize`: cria a Config; `admin` vira o admin do protocolo
+pub fn initialize(admin: Pubkey, args: ConfigArgs) -> Instruction {
+    build(
+        accounts::Initialize {
+            admin,
+            config: pda::config_address(),
+            system_program: system_program::ID,
+            event_authority: pda::event_authority_address(),
+            program: snake_betting::ID,
+        },
+        instruction::Initialize { args },
+    )
+}
+
+/// `update_config`: admin atualiza os parâmetros globais
+pub fn update_config(admin: Pubkey, args: ConfigArgs) -> Instruction {
+    build(
+        accounts::UpdateConfig {
+            admin,
+            config: pda::config_address(),
+            event_authority: pda::event_authority_address(),
+            program: snake_betting::ID,
+        },
+        instruction::UpdateConfig { args },
+    )
+}
+
+/// `set_admin`: admin passa a autoridade pra `new_admin`
+pub fn set_admin(admin: Pubkey, new_admin: Pubkey) -> Instruction {
+    build(
+        accounts::UpdateConfig {
+            admin,
+            config: pda::config_address(),
+            event_authority: pda::event_authority_address(),
+            program: snake_betting::ID,
+        },
+        instruction::SetAdmin { new_admin },
+    )
+}
+
+/// `set_paused`: admin pausa/retoma o protocolo (e, opcionalmente, os saques)
+pub fn set_paused(admin: Pubkey, paused: bool, withdrawals_paused: bool) -> Instruction {
+    build(
+        accounts::UpdateConfig {
+            admin,
+            config: pda::config_address(),
+            event_authority: pda::event_authority_address(),
+            program: snake_betting::ID,
+        },
+        instruction::SetPaused {
+            paused,
+            withdrawals_paused,
+        },
+    )
+}
+
+/// `set_match_suspended`: árbitro ou admin suspende/retoma a partida
+/// (e, opcionalmente, os saques dela)
+pub fn set_match_suspended(
+    match_account: Pubkey,
+    authority: Pubkey,
+    suspended: bool,
+    withdrawals_suspended: bool,
+) -> Instruction {
+    build(
+        accounts::SetMatchSuspended {
+            authority,
+            config: pda::config_address(),
+            match_account,
+            event_authority: pda::event_authority_address(),
+            program: snake_betting::ID,
+        },
+        instruction::SetMatchSuspended {
+            suspended,
+            withdrawals_suspended,
+        },
+    )
+}
+
+/// `init_match_counter`: cria o contador de partidas do árbitro
+pub fn init_match_counter(arbiter: Pubkey) -> Instruction {
+    build(
+        accounts::InitMatchCounter {
+            arbiter,
+            match_counter: pda::match_counter_address(&arbiter),
+            system_program: system_program::ID,
+            event_authority: pda::event_authority_address(),
+            program: snake_betting::ID,
+        },
+        instruction::InitMatchCounter {},
+    )
+}
+
+/// `create_match`: cria a partida `args.id` (precisa ser o `next_id` do contador)
+pub fn create_match(arbiter: Pubkey, args: CreateMatchArgs, spl: Option<SplMint>) -> Instruction {
+    let match_account = pda::match_address(&arbiter, args.id);
+    let (mint, token_vault, token_program) = spl_accounts(&match_account, spl);
+
+    build(
+        accounts::CreateMatch {
+            arbiter,
+            config: pda::config_address(),
+            match_counter: pda::match_counter_address(&arbiter),
+            match_account,
+            vault: pda::vault_address(&match_account),
+            mint,
+            token_vault,
+            token_program,
+            system_program: system_program::ID,
+            event_authority: pda::event_authority_address(),
+            program: snake_betting::ID,
+        },
+        instruction::CreateMatch { args },
+    )
+}
+
+/// `join_as_player`: jogador deposita o stake
+pub fn join_as_player(match_account: Pubkey, player: Pubkey, spl: Option<SplMint>) -> Instruction {
+    let (mint, token_vault, token_program) = spl_accounts(&match_account, spl);
+
+    build(
+        accounts::JoinAsPlayer {
+            player,
+            config: pda::config_address(),
+            match_account,
+            vault: pda::vault_address(&match_account),
+            mint,
+            token_vault,
+            player_token_account: spl.map(|spl| spl.token_account(&player)),
+            token_program,
+            system_program: system_program::ID,
+            event_authority: pda::event_authority_address(),
+            program: snake_betting::ID,
+        },
+        instruction::JoinAsPlayer {},
+    )
+}
+
+/// `place_bet`: apostador aposta `amount` em `side`
+pub fn place_bet(
+    match_account: Pubkey,
+    bettor: Pubkey,
+    side: Side,
+    amount: u64,
+    spl: Option<SplMint>,
+) -> Instruction {
+    let (mint, token_vault, token_program) = spl_accounts(&match_account, spl);
+
+    build(
+        accounts::PlaceBet {
+            bettor,
+            config: pda::config_address(),
+            match_account,
+            vault: pda::vault_address(&match_account),
+            participant: pda::participant_address(&match_account, &bettor),
+            mint,
+            token_vault,
+            bettor_token_account: spl.map(|spl| spl.token_account(&bettor)),
+            token_program,
+            system_program: system_program::ID,
+            event_authority: pda::event_authority_address(),
+            program: snake_betting::ID,
+        },
+        instruction::PlaceBet { side, amount },
+    )
+}
+
+/// `start_match`: árbitro inicia o jogo e fecha as apostas
+pub fn start_match(match_account: Pubkey, arbiter: Pubkey) -> Instruction {
+    build(
+        accounts::ArbiterDecision {
+            arbiter,
+            match_account,
+            event_authority: pda::event_authority_address(),
+            program: snake_betting::ID,
+        },
+        instruction::StartMatch {},
+    )
+}
+
+/// `declare_winner`: árbitro declara o vencedor; `treasury` é a tesouraria da Config
+pub fn declare_winner(
+    match_account: Pubkey,
+    arbiter: Pubkey,
+    treasury: Pubkey,
+    winner: Side,
+    spl: Option<SplMint>,
+) -> Instruction {
+    let (mint, token_vault, token_program) = spl_accounts(&match_account, spl);
+
+    build(
+        accounts::DeclareWinner {
+            arbiter,
+            config: pda::config_address(),
+            treasury,
+            match_account,
+            vault: pda::vault_address(&match_account),
+            mint,
+            token_vault,
+            treasury_token_account: spl.map(|spl| spl.token_account(&treasury)),
+            arbiter_token_account: spl.map(|spl| spl.token_account(&arbiter)),
+            token_program,
+            system_program: system_program::ID,
+            event_authority: pda::event_authority_address(),
+            program: snake_betting::ID,
+        },
+        instruction::DeclareWinner { winner },
+    )
+}
+
+/// `declare_draw`: árbitro declara empate
+pub fn declare_draw(match_account: Pubkey, arbiter: Pubkey) -> Instruction {
+    build(
+        accounts::ArbiterDecision {
+            arbiter,
+            match_account,
+            event_authority: pda::event_authority_address(),
+            program: snake_betting::ID,
+        },
+        instruction::DeclareDraw {},
+    )
+}
+
+/// `declare_walkover`: árbitro declara W.O.
+pub fn declare_walkover(match_account: Pubkey, arbiter: Pubkey) -> Instruction {
+    build(
+        accounts::ArbiterDecision {
+            arbiter,
+            match_account,
+            event_authority: pda::event_authority_address(),
+            program: snake_betting::ID,
+        },
+        instruction::DeclareWalkover {},
+    )
+}
+
+/// `claim_walkover`: jogador que depositou reivindica o W.O.
+pub fn claim_walkover(match_account: Pubkey, player: Pubkey) -> Instruction {
+    build(
+        accounts::ClaimWalkover {
+            player,
+            match_account,
+            event_authority: pda::event_authority_address(),
+            program: snake_betting::ID,
+        },
+        instruction::ClaimWalkover {},
+    )
+}
+
+/// `withdraw_winner_stake`: jogador vencedor saca os stakes
+pub fn withdraw_winner_stake(
+    match_account: Pubkey,
+    winner: Pubkey,
+    spl: Option<SplMint>,
+) -> Instruction {
+    let (mint, token_vault, token_program) = spl_accounts(&match_account, spl);
+
+    build(
+        accounts::WithdrawWinnerStake {
+            winner,
+            config: pda::config_address(),
+            match_account,
+            vault: pda::vault_address(&match_account),
+            mint,
+            token_vault,
+            winner_token_account: spl.map(|spl| spl.token_account(&winner)),
+            token_program,
+            system_program: system_program::ID,
+            event_authority: pda::event_authority_address(),
+            program: snake_betting::ID,
+        },
+        instruction::WithdrawWinnerStake {},
+    )
+}
+
+/// `claim_bet_payout`: apostador do lado vencedor saca o prêmio
+pub fn claim_bet_payout(
+    match_account: Pubkey,
+    bettor: Pubkey,
+    spl: Option<SplMint>,
+) -> Instruction {
+    let (mint, token_vault, token_program) = spl_accounts(&match_account, spl);
+
+    build(
+        accounts::ClaimBetPayout {
+            bettor,
+            config: pda::config_address(),
+            match_account,
+            vault: pda::vault_address(&match_account),
+            participant: pda::participant_address(&match_account, &bettor),
+            mint,
+            token_vault,
+            bettor_token_account: spl.map(|spl| spl.token_account(&bettor)),
+            token_program,
+            system_program: system_program::ID,
+            event_authority: pda::event_authority_address(),
+            program: snake_betting::ID,
+        },
+        instruction::ClaimBetPayout {},
+    )
+}
+
+/// `cancel_match`: árbitro cancela a partida
+pub fn cancel_match(match_account: Pubkey, arbiter: Pubkey) -> Instruction {
+    build(
+        accounts::CancelMatch {
+            arbiter,
+            match_account,
+            event_authority: pda::event_authority_address(),
+            program: snake_betting::ID,
+        },
+        instruction::CancelMatch {},
+    )
+}
+
+/// `expire_match`: qualquer um expira uma partida fora do prazo
+pub fn expire_match(match_account: Pubkey) -> Instruction {
+    build(
+        accounts::ExpireMatch {
+            match_account,
+            event_authority: pda::event_authority_address(),
+            program: snake_betting::ID,
+        },
+        instruction::ExpireMatch {},
+    )
+}
+
+/// `refund_player`: devolve o stake de `player` (qualquer um pode enviar)
+pub fn refund_player(match_account: Pubkey, player: Pubkey, spl: Option<SplMint>) -> Instruction {
+    let (mint, token_vault, token_program) = spl_accounts(&match_account, spl);
+
+    build(
+        accounts::RefundPlayer {
+            player,
+            config: pda::config_address(),
+            match_account,
+            vault: pda::vault_address(&match_account),
+            mint,
+            token_vault,
+            player_token_account: spl.map(|spl| spl.token_account(&player)),
+            token_program,
+            system_program: system_program::ID,
+            event_authority: pda::event_authority_address(),
+            program: snake_betting::ID,
+        },
+        instruction::RefundPlayer {},
+    )
+}
+
+/// `refund_bet`: devolve a aposta de `bettor` (qualquer um pode enviar)
+pub fn refund_bet(match_account: Pubkey, bettor: Pubkey, spl: Option<SplMint>) -> Instruction {
+    let (mint, token_vault, token_program) = spl_accounts(&match_account, spl);
+
+    build(
+        accounts::RefundBet {
+            bettor,
+            config: pda::config_address(),
+            match_account,
+            vault: pda::vault_address(&match_account),
+            participant: pda::participant_address(&match_account, &bettor),
+            mint,
+            token_vault,
+            bettor_token_account: spl.map(|spl| spl.token_account(&bettor)),
+            token_program,
+            system_program: system_program::ID,
+            event_authority: pda::event_authority_address(),
+            program: snake_betting::ID,
+        },
+        instruction::RefundBet {},
+    )
+}
+
+/// `claim_unmatched_pool`: manda o pool sem apostas no vencedor pra `recipient`
+/// (jogador vencedor ou tesouraria, conforme a política da partida)
+pub fn claim_unmatched_pool(
+    match_account: Pubkey,
+    recipient: Pubkey,
+    spl: Option<SplMint>,
+) -> Instruction {
+    let (mint, token_vault, token_program) = spl_accounts(&match_account, spl);
+
+    build(
+        accounts::ClaimUnmatchedPool {
+            recipient,
+            config: pda::config_address(),
+            match_account,
+            vault: pda::vault_address(&match_account),
+            mint,
+            token_vault,
+            recipient_token_account: spl.map(|spl| spl.token_account(&recipient)),
+            token_program,
+            system_program: system_program::ID,
+            event_authority: pda::event_authority_address(),
+            program: snake_betting::ID,
+        },
+        instruction::ClaimUnmatchedPool {},
+    )
+}
+
+/// `sweep_dust`: manda a sobra de arredondamento do pool pra `recipient`
+/// (jogador vencedor ou tesouraria, conforme a política da partida)
+pub fn sweep_dust(match_account: Pubkey, recipient: Pubkey, spl: Option<SplMint>) -> Instruction {
+    let (mint, token_vault, token_program) = spl_accounts(&match_account, spl);
+
+    build(
+        accounts::SweepDust {
+            recipient,
+            config: pda::config_address(),
+            match_account,
+            vault: pda::vault_address(&match_account),
+            mint,
+            token_vault,
+            recipient_token_account: spl.map(|spl| spl.token_account(&recipient)),
+            token_program,
+            system_program: system_program::ID,
+            event_authority: pda::event_authority_address(),
+            program: snake_betting::ID,
+        },
+        instruction::SweepDust {},
+    )
+}
+
+/// `sweep_unclaimed`: árbitro ou admin manda pra tesouraria o que não foi
+/// sacado até o prazo de saque
+pub fn sweep_unclaimed(
+    match_account: Pubkey,
+    authority: Pubkey,
+    treasury: Pubkey,
+    spl: Option<SplMint>,
+) -> Instruction {
+    let (mint, token_vault, token_program) = spl_accounts(&match_account, spl);
+
+    build(
+        accounts::SweepUnclaimed {
+            authority,
+            config: pda::config_address(),
+            treasury,
+            match_account,
+            vault: pda::vault_address(&match_account),
+            mint,
+            token_vault,
+            treasury_token_account: spl.map(|spl| spl.token_account(&treasury)),
+            token_program,
+            system_program: system_program::ID,
+            event_authority: pda::event_authority_address(),
+            program: snake_betting::ID,
+        },
+        instruction::SweepUnclaimed {},
+    )
+}
+
+/// `close_participant`: fecha a participação de `bettor` e devolve o rent
+pub fn close_participant(match_account: Pubkey, bettor: Pubkey) -> Instruction {
+    build(
+        accounts::CloseParticipant {
+            bettor,
+            match_account,
+            participant: pda::participant_address(&match_account, &bettor),
+            event_authority: pda::event_authority_address(),
+            program: snake_betting::ID,
+        },
+        instruction::CloseParticipant {},
+    )
+}
+
+/// `close_match`: fecha a partida liquidada; o rent volta pro `arbiter` dela
+pub fn close_match(match_account: Pubkey, arbiter: Pubkey, spl: Option<SplMint>) -> Instruction {
+    let (mint, token_vault, token_program) = spl_accounts(&match_account, spl);
+
+    build(
+        accounts::CloseMatch {
+            arbiter,
+            match_account,
+            vault: pda::vault_address(&match_account),
+            mint,
+            token_vault,
+            arbiter_token_account: spl.map(|spl| spl.token_account(&arbiter)),
+            token_program,
+            system_program: system_program::ID,
+            event_authority: pda::event_authority_address(),
+            program: snake_betting::ID,
+        },
+        instruction::CloseMatch {},
+    )
+}
@@ -0,0 +1,22 @@
+//! Cliente Rust off-chain do programa `snake_betting`.
+//!
+//! - [`pda`]: derivação das PDAs (Config, contador, Match, vaults, Participant).
+//! - [`instructions`]: construtores tipados de todas as instruções.
+//! - [`accounts`]: desserialização das contas lidas via RPC.
+//! - [`error`]: tradução dos códigos de erro do programa.
+//! - [`settlement`]: cálculo de taxas e prêmios, o mesmo usado on-chain.
+//!
+//! Os tipos do programa (contas, argumentos e enums) são reexportados aqui,
+//! então quem usa o cliente não precisa depender do crate do programa.
+
+pub mod accounts;
+pub mod error;
+pub mod instructions;
+pub mod pda;
+
+pub use error::ClientError;
+pub use instructions::SplMint;
+pub use snake_betting::{
+    settlement, Config, ConfigArgs, CreateMatchArgs, CustomError, Match, MatchCounter,
+    MatchStatus, Participant, Side, UnmatchedPoolPolicy, ID,
+};
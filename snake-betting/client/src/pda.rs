@@ -0,0 +1,46 @@
+//! Derivação das PDAs do programa, com as mesmas seeds usadas on-chain.
+
+use anchor_lang::prelude::Pubkey;
+
+/// Configuração global do protocolo (seeds = ["config"])
+pub fn config_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"config"], &snake_betting::ID).0
+}
+
+/// Contador de partidas de um árbitro (seeds = ["match_counter", arbiter])
+pub fn match_counter_address(arbiter: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"match_counter", arbiter.as_ref()], &snake_betting::ID).0
+}
+
+/// Partida `id` de um árbitro (seeds = ["match", arbiter, id em little-endian])
+pub fn match_address(arbiter: &Pubkey, id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"match", arbiter.as_ref(), &id.to_le_bytes()],
+        &snake_betting::ID,
+    )
+    .0
+}
+
+/// Vault de lamports da partida (seeds = ["vault", match])
+pub fn vault_address(match_pubkey: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"vault", match_pubkey.as_ref()], &snake_betting::ID).0
+}
+
+/// Vault de tokens da partida, só em partidas SPL (seeds = ["token_vault", match])
+pub fn token_vault_address(match_pubkey: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"token_vault", match_pubkey.as_ref()], &snake_betting::ID).0
+}
+
+/// Participação de um apostador numa partida (seeds = ["participant", match, bettor])
+pub fn participant_address(match_pubkey: &Pubkey, bettor: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"participant", match_pubkey.as_ref(), bettor.as_ref()],
+        &snake_betting::ID,
+    )
+    .0
+}
+
+/// Autoridade dos eventos CPI do Anchor (seeds = ["__event_authority"])
+pub fn event_authority_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"__event_authority"], &snake_betting::ID).0
+}
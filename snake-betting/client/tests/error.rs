@@ -0,0 +1,50 @@
+//! Tabela de erros do cliente contra o `CustomError` do programa.
+
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use snake_betting_client::error::program_error;
+use snake_betting_client::{ClientError, CustomError};
+
+#[test]
+fn program_errors_follow_the_declaration_order() {
+    // a tabela vai de ERROR_CODE_OFFSET até o primeiro código sem erro; cada
+    // posição precisa dar exatamente o código que o programa devolve
+    let mut count = 0;
+    while let Some(err) = program_error(ERROR_CODE_OFFSET + count) {
+        assert_eq!(
+            u32::from(err),
+            ERROR_CODE_OFFSET + count,
+            "{} fora de ordem na tabela do cliente",
+            err.name()
+        );
+        count += 1;
+    }
+    assert!(count > 0);
+
+    // o primeiro código depois do último erro é desconhecido
+    let next = ERROR_CODE_OFFSET + count;
+    assert!(matches!(ClientError::from_code(next), ClientError::Unknown(code) if code == next));
+}
+
+#[test]
+fn from_code_returns_the_program_error_with_the_same_name() {
+    for err in [
+        CustomError::InvalidDeadline,
+        CustomError::BetsClosed,
+        CustomError::NoBetsOnWinnerSide,
+        CustomError::MatchWithdrawalsSuspended,
+    ] {
+        match ClientError::from_code(err.into()) {
+            ClientError::Program(got) => assert_eq!(got.name(), err.name()),
+            other => panic!("{} virou {other}", err.name()),
+        }
+    }
+}
+
+#[test]
+fn codes_below_the_offset_are_anchor_errors() {
+    // 3012 = AccountNotInitialized
+    assert!(matches!(
+        ClientError::from_code(3012),
+        ClientError::Anchor(3012)
+    ));
+}
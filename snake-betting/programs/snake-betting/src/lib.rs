@@ -1,41 +1,272 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::spl_token_2022::extension::transfer_fee::TransferFeeConfig;
+use anchor_spl::token_interface::{
+    self, CloseAccount, HarvestWithheldTokensToMint, Mint, TokenAccount, TokenInterface,
+    TransferChecked,
+};
+
+pub mod settlement;
 
 declare_id!("HBHeroLarYj7jgzWHfmzbwbVG2dUGgzM5CbTP7pJg3K1");
 
+/// Denominador das taxas em basis points (10_000 bps = 100%)
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Teto absoluto da soma das taxas (protocolo + árbitro), em basis points.
+/// O admin pode configurar um teto menor em `Config.max_fee_bps`.
+pub const MAX_FEE_BPS: u16 = 2_000;
+
 #[program]
 pub mod snake_betting {
     use super::*;
 
-    /// Inicialização dummy só pra manter compatibilidade.
-    /// Não faz nada por enquanto.
-    pub fn initialize(_ctx: Context<Initialize>) -> Result<()> {
+    /// Cria a configuração global do protocolo (`Config`).
+    ///
+    /// Só pode ser chamada uma vez; quem assina vira o admin do protocolo.
+    /// Deve ser chamada logo depois do deploy.
+    pub fn initialize(ctx: Context<Initialize>, args: ConfigArgs) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        config.admin = ctx.accounts.admin.key();
+        config.bump = ctx.bumps.config;
+        config.paused = false;
+        config.withdrawals_paused = false;
+        config.withdrawals_paused_at = 0;
+        config.withdrawals_paused_secs = 0;
+        config.apply(args.clone())?;
+
+        emit_cpi!(ConfigUpdated {
+            admin: config.admin,
+            args,
+        });
+
         Ok(())
     }
 
-    /// Cria uma nova partida (Match) de aposta da cobrinha.
+    /// Admin atualiza os parâmetros globais do protocolo.
+    pub fn update_config(ctx: Context<UpdateConfig>, args: ConfigArgs) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        // Só o admin pode atualizar
+        require!(ctx.accounts.admin.key() == config.admin, CustomError::NotAdmin);
+
+        config.apply(args.clone())?;
+
+        emit_cpi!(ConfigUpdated {
+            admin: config.admin,
+            args,
+        });
+
+        Ok(())
+    }
+
+    /// Admin passa a autoridade do protocolo pra outra wallet.
+    pub fn set_admin(ctx: Context<UpdateConfig>, new_admin: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        // Só o admin pode trocar o admin
+        require!(ctx.accounts.admin.key() == config.admin, CustomError::NotAdmin);
+
+        let previous_admin = config.admin;
+        config.admin = new_admin;
+
+        emit_cpi!(AdminChanged {
+            previous_admin,
+            new_admin,
+        });
+
+        Ok(())
+    }
+
+    /// Admin pausa (ou retoma) o protocolo inteiro.
+    ///
+    /// - `paused`: bloqueia criação de partidas, depósitos e apostas.
+    /// - `withdrawals_paused`: bloqueia também saques, reembolsos e varreduras.
+    ///
+    /// Resoluções e cancelamentos continuam liberados, pra partidas em andamento
+    /// poderem ser encerradas. O tempo com saques pausados não conta no prazo de
+    /// saque das partidas resolvidas (ver `Match::claim_deadline_at`).
+    pub fn set_paused(
+        ctx: Context<UpdateConfig>,
+        paused: bool,
+        withdrawals_paused: bool,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+
+        // Só o admin pode pausar
+        require!(ctx.accounts.admin.key() == config.admin, CustomError::NotAdmin);
+
+        // Guarda quando os saques pararam e, ao retomar, soma a pausa no total
+        let now = Clock::get()?.unix_timestamp;
+        if withdrawals_paused && !config.withdrawals_paused {
+            config.withdrawals_paused_at = now;
+        } else if !withdrawals_paused && config.withdrawals_paused {
+            config.withdrawals_paused_secs = config.withdrawals_paused_secs_at(now);
+        }
+
+        config.paused = paused;
+        config.withdrawals_paused = withdrawals_paused;
+
+        emit_cpi!(PauseUpdated {
+            admin: ctx.accounts.admin.key(),
+            paused,
+            withdrawals_paused,
+        });
+
+        Ok(())
+    }
+
+    /// Árbitro da partida ou admin do protocolo suspende (ou retoma) uma partida.
     ///
-    /// - `id`: identificador da partida (pode ser qualquer número, você decide off-chain)
-    /// - `stake_lamports`: quanto cada jogador precisa depositar
-    /// - `deadline`: timestamp mínimo (Unix) para poder declarar o vencedor
-    /// - `player_a`, `player_b`: wallets dos dois jogadores principais
-    pub fn create_match(
-        ctx: Context<CreateMatch>,
-        id: u64,
-        stake_lamports: u64,
-        deadline: i64,
-        player_a: Pubkey,
-        player_b: Pubkey,
+    /// - `suspended`: bloqueia depósitos e apostas nessa partida.
+    /// - `withdrawals_suspended`: bloqueia também saques, reembolsos e varreduras.
+    ///
+    /// O tempo com saques suspensos depois da resolução não conta no prazo de
+    /// saque: ao retomar, `claim_deadline` é adiado pelo mesmo tanto.
+    pub fn set_match_suspended(
+        ctx: Context<SetMatchSuspended>,
+        suspended: bool,
+        withdrawals_suspended: bool,
     ) -> Result<()> {
+        let m = &mut ctx.accounts.match_account;
+
+        // Só o árbitro ou o admin
+        let authority = ctx.accounts.authority.key();
+        require!(
+            authority == m.arbiter || authority == ctx.accounts.config.admin,
+            CustomError::NotMatchAuthority
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        if withdrawals_suspended && !m.withdrawals_suspended {
+            m.withdrawals_suspended_at = now;
+        } else if !withdrawals_suspended && m.withdrawals_suspended {
+            m.extend_claim_deadline(now)?;
+        }
+
+        m.suspended = suspended;
+        m.withdrawals_suspended = withdrawals_suspended;
+
+        emit_cpi!(MatchSuspensionUpdated {
+            match_pubkey: m.key(),
+            match_id: m.id,
+            authority,
+            suspended,
+            withdrawals_suspended,
+        });
+
+        Ok(())
+    }
+
+    /// Cria o contador de partidas do árbitro.
+    ///
+    /// Só precisa ser chamado uma vez por árbitro. A partir daí, cada `create_match`
+    /// usa `next_id` como id da partida e incrementa o contador.
+    pub fn init_match_counter(ctx: Context<InitMatchCounter>) -> Result<()> {
+        let counter = &mut ctx.accounts.match_counter;
+
+        counter.arbiter = ctx.accounts.arbiter.key();
+        counter.next_id = 0;
+        counter.bump = ctx.bumps.match_counter;
+
+        emit_cpi!(MatchCounterInitialized {
+            arbiter: counter.arbiter,
+            match_counter: counter.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Cria uma nova partida (Match) de aposta da cobrinha.
+    ///
+    /// Os parâmetros estão documentados em `CreateMatchArgs`.
+    pub fn create_match(ctx: Context<CreateMatch>, args: CreateMatchArgs) -> Result<()> {
+        let CreateMatchArgs {
+            id,
+            stake_lamports,
+            fund_by,
+            bets_close_at,
+            earliest_resolution,
+            resolve_by,
+            player_a,
+            player_b,
+            unmatched_pool_policy,
+            arbiter_fee_bps,
+        } = args;
+
+        // protocolo pausado não abre partidas novas
+        require!(!ctx.accounts.config.paused, CustomError::ProtocolPaused);
+
         let clock = Clock::get()?;
 
-        // deadline tem que estar no futuro
+        // fechamento das apostas tem que estar no futuro
         require!(
-            deadline > clock.unix_timestamp,
+            bets_close_at > clock.unix_timestamp,
             CustomError::InvalidDeadline
         );
 
-        // stake tem que ser > 0
+        // prazo de depósito tem que estar no futuro e não depois do fechamento das apostas
+        require!(
+            fund_by > clock.unix_timestamp && fund_by <= bets_close_at,
+            CustomError::InvalidFundBy
+        );
+
+        // resultado só pode sair depois que as apostas fecharem
+        require!(
+            earliest_resolution >= bets_close_at,
+            CustomError::InvalidEarliestResolution
+        );
+
+        // prazo de resolução tem que vir depois do primeiro momento de resolução
+        require!(
+            resolve_by > earliest_resolution,
+            CustomError::InvalidResolveBy
+        );
+
+        // partida em token SPL precisa do mint, do vault e do programa de tokens
+        let mint = ctx.accounts.mint.as_ref().map(|mint| mint.key());
+        if mint.is_some() {
+            require!(
+                ctx.accounts.token_vault.is_some() && ctx.accounts.token_program.is_some(),
+                CustomError::MissingTokenAccounts
+            );
+        }
+
+        // stake tem que ser > 0 e respeitar o mínimo do protocolo
+        // (os mínimos da Config são em lamports, então só valem pra partidas em SOL)
+        let config = &ctx.accounts.config;
         require!(stake_lamports > 0, CustomError::InvalidStake);
+        require!(
+            mint.is_some() || stake_lamports >= config.min_stake_lamports,
+            CustomError::StakeBelowMinimum
+        );
+
+        // partida não pode ficar aberta mais do que o protocolo permite
+        let duration = resolve_by
+            .checked_sub(clock.unix_timestamp)
+            .ok_or(CustomError::MathOverflow)?;
+        require!(
+            duration <= config.max_match_duration,
+            CustomError::MatchTooLong
+        );
+
+        // taxa do protocolo + taxa do árbitro não podem passar do teto global
+        let total_fee_bps = config
+            .protocol_fee_bps
+            .checked_add(arbiter_fee_bps)
+            .ok_or(CustomError::MathOverflow)?;
+        require!(total_fee_bps <= config.max_fee_bps, CustomError::FeeTooHigh);
+        let protocol_fee_bps = config.protocol_fee_bps;
+        let claim_window = config.claim_window;
+
+        // id tem que ser exatamente o próximo do contador do árbitro
+        let counter = &mut ctx.accounts.match_counter;
+        require!(id == counter.next_id, CustomError::MatchIdMismatch);
+
+        counter.next_id = counter
+            .next_id
+            .checked_add(1)
+            .ok_or(CustomError::MathOverflow)?;
 
         let m = &mut ctx.accounts.match_account;
 
@@ -47,7 +278,10 @@ pub mod snake_betting {
         m.total_side_a = 0;
         m.total_side_b = 0;
 
-        m.deadline = deadline;
+        m.bets_close_at = bets_close_at;
+        m.earliest_resolution = earliest_resolution;
+        m.resolve_by = resolve_by;
+        m.fund_by = fund_by;
         m.status = MatchStatus::Created;
         m.winner = None;
 
@@ -58,7 +292,75 @@ pub mod snake_betting {
         m.id = id;
         m.player_a_deposited = false;
         m.player_b_deposited = false;
+        m.player_a_stake = 0;
+        m.player_b_stake = 0;
         m.stakes_withdrawn = false;
+        m.stake_pot = 0;
+        m.unmatched_pool_policy = unmatched_pool_policy;
+        m.unmatched_pool_claimed = false;
+        m.participants_side_a = 0;
+        m.participants_side_b = 0;
+        m.payouts_claimed = 0;
+        m.dust_swept = false;
+
+        // prazo de saque também fica congelado; o relógio só começa na resolução
+        m.claim_window = claim_window;
+        m.claim_deadline = 0;
+        m.unclaimed_swept = false;
+
+        m.suspended = false;
+        m.withdrawals_suspended = false;
+        m.withdrawals_suspended_at = 0;
+        m.paused_secs_at_resolution = 0;
+
+        // taxas ficam congeladas na Match (mudanças na Config não afetam partidas abertas)
+        m.protocol_fee_bps = protocol_fee_bps;
+        m.arbiter_fee_bps = arbiter_fee_bps;
+        m.protocol_fee = 0;
+        m.arbiter_fee = 0;
+        m.payout_pool = 0;
+
+        // None = SOL nativo; Some = mint SPL, com os tokens no `token_vault`
+        m.mint = mint;
+
+        // Escrow começa vazio: nenhuma obrigação ainda
+        m.vault_bump = ctx.bumps.vault;
+        m.escrowed = 0;
+        m.started_at = 0;
+
+        // Vault de lamports precisa do rent mínimo pra existir (fica lá até o close_match)
+        let rent_floor = Rent::get()?.minimum_balance(0);
+        let vault = &ctx.accounts.vault;
+        let missing = rent_floor.saturating_sub(vault.lamports());
+        if missing > 0 {
+            let ix = anchor_lang::solana_program::system_instruction::transfer(
+                &ctx.accounts.arbiter.key(),
+                &vault.key(),
+                missing,
+            );
+
+            anchor_lang::solana_program::program::invoke(
+                &ix,
+                &[
+                    ctx.accounts.arbiter.to_account_info(),
+                    vault.to_account_info(),
+                    ctx.accounts.system_program.to_account_info(),
+                ],
+            )?;
+        }
+
+        emit_cpi!(MatchCreated {
+            match_pubkey: ctx.accounts.match_account.key(),
+            match_id: id,
+            arbiter: ctx.accounts.arbiter.key(),
+            player_a,
+            player_b,
+            stake_lamports,
+            mint,
+            bets_close_at,
+            earliest_resolution,
+            resolve_by,
+        });
 
         Ok(())
 
@@ -80,12 +382,19 @@ pub mod snake_betting {
         // Lemos os dados da Match de forma imutável primeiro
         let m_immut = &ctx.accounts.match_account;
 
+        // Nem o protocolo nem a partida podem estar pausados
+        m_immut.check_deposits_open(&ctx.accounts.config)?;
+
         // Status tem que permitir depósito
         require!(
             m_immut.status == MatchStatus::Created || m_immut.status == MatchStatus::Funded,
             CustomError::InvalidStatus
         );
 
+        // Depósitos só até o prazo de depósito
+        let clock = Clock::get()?;
+        require!(clock.unix_timestamp < m_immut.fund_by, CustomError::FundingClosed);
+
         // Verifica se é player A ou B e se já não depositou
         let mut is_player_a = false;
         let mut is_player_b = false;
@@ -100,20 +409,24 @@ pub mod snake_betting {
             return err!(CustomError::NotAPlayer);
         }
 
-        // Transferência de SOL: player → conta Match (escrow)
-        let ix = anchor_lang::solana_program::system_instruction::transfer(
-            &player_key,
-            &m_immut.key(),
-            m_immut.stake_lamports,
-        );
-
-        anchor_lang::solana_program::program::invoke(
-            &ix,
-            &[
-                ctx.accounts.player.to_account_info(),
-                ctx.accounts.match_account.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
+        // Transferência do stake: player → escrow da Match (SOL ou token)
+        let stake_lamports = m_immut.stake_lamports;
+        let token = token_escrow(
+            m_immut,
+            &ctx.accounts.mint,
+            &ctx.accounts.token_vault,
+            &ctx.accounts.player_token_account,
+            &ctx.accounts.token_program,
+            player_key,
+        )?;
+        // Com taxa de transferência (Token-2022) o escrow recebe menos que o stake
+        let received = escrow_deposit(
+            &mut ctx.accounts.match_account,
+            &ctx.accounts.vault,
+            &ctx.accounts.player,
+            &ctx.accounts.system_program,
+            token,
+            stake_lamports,
         )?;
 
         // Agora pegamos a match como mutável pra atualizar flags e status
@@ -121,16 +434,33 @@ pub mod snake_betting {
 
         if is_player_a {
             m.player_a_deposited = true;
+            m.player_a_stake = received;
         }
         if is_player_b {
             m.player_b_deposited = true;
+            m.player_b_stake = received;
         }
 
+        // Soma o que foi realmente depositado (separado do pool de apostas)
+        m.stake_pot = m
+            .stake_pot
+            .checked_add(received)
+            .ok_or(CustomError::MathOverflow)?;
+
         // Se os dois depositaram, muda status para Funded
         if m.player_a_deposited && m.player_b_deposited {
             m.status = MatchStatus::Funded;
         }
 
+        emit_cpi!(PlayerJoined {
+            match_pubkey: m.key(),
+            match_id: m.id,
+            player: player_key,
+            amount: received,
+            stake_pot: m.stake_pot,
+            status: m.status.clone(),
+        });
+
         Ok(())
     }
 
@@ -140,7 +470,8 @@ pub mod snake_betting {
     /// - `amount`: quanto ele está apostando (em lamports)
     ///
     /// A conta `Participant` é criada (se não existir) ou atualizada (se já existir),
-    /// e o valor apostado é transferido para a conta Match (escrow).
+    /// e o valor apostado é transferido para o vault da partida (escrow).
+    /// Apostas repetidas acumulam, mas sempre no mesmo lado da primeira aposta.
     pub fn place_bet(
         ctx: Context<PlaceBet>,
         side: Side,
@@ -149,8 +480,19 @@ pub mod snake_betting {
         let bettor_key = ctx.accounts.bettor.key();
         let m_immut = &ctx.accounts.match_account;
 
-        // Valor precisa ser > 0
+        // Nem o protocolo nem a partida podem estar pausados
+        m_immut.check_deposits_open(&ctx.accounts.config)?;
+
+        // Valor precisa ser > 0 e respeitar o mínimo do protocolo
+        // (o mínimo da Config é em lamports, então só vale pra partidas em SOL)
         require!(amount > 0, CustomError::InvalidAmount);
+        require!(
+            m_immut.mint.is_some() || amount >= ctx.accounts.config.min_bet_lamports,
+            CustomError::BetBelowMinimum
+        );
+
+        // Com o jogo já começado as apostas estão fechadas
+        require!(m_immut.status != MatchStatus::InProgress, CustomError::BetsClosed);
 
         // Status da partida tem que permitir apostas
         require!(
@@ -159,36 +501,80 @@ pub mod snake_betting {
             CustomError::InvalidStatus
         );
 
-        // Apostas só são aceitas antes do deadline
+        // Apostas só são aceitas antes do fechamento das apostas
         let clock = Clock::get()?;
-        require!(clock.unix_timestamp < m_immut.deadline, CustomError::BetsClosed);
+        require!(clock.unix_timestamp < m_immut.bets_close_at, CustomError::BetsClosed);
 
-        // Transferência de SOL: bettor -> Match (escrow)
-        let ix = anchor_lang::solana_program::system_instruction::transfer(
-            &bettor_key,
-            &m_immut.key(),
-            amount,
+        // Se o prazo de depósito passou sem os dois stakes, a partida não vai acontecer
+        require!(
+            m_immut.status == MatchStatus::Funded || clock.unix_timestamp < m_immut.fund_by,
+            CustomError::BetsClosed
+        );
+
+        // Conta recém-criada pelo init_if_needed ainda tem o bettor zerado
+        let is_new_participant = ctx.accounts.participant.bettor == Pubkey::default();
+
+        // Quem já apostou não pode trocar de lado
+        require!(
+            is_new_participant || ctx.accounts.participant.side == side,
+            CustomError::SideMismatch
         );
 
-        anchor_lang::solana_program::program::invoke(
-            &ix,
-            &[
-                ctx.accounts.bettor.to_account_info(),
-                ctx.accounts.match_account.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
+        // Transferência da aposta: bettor -> escrow da Match (SOL ou token)
+        let token = token_escrow(
+            m_immut,
+            &ctx.accounts.mint,
+            &ctx.accounts.token_vault,
+            &ctx.accounts.bettor_token_account,
+            &ctx.accounts.token_program,
+            bettor_key,
+        )?;
+        // Com taxa de transferência (Token-2022) o escrow recebe menos que `amount`;
+        // daqui pra frente só vale o que foi realmente recebido
+        let amount = escrow_deposit(
+            &mut ctx.accounts.match_account,
+            &ctx.accounts.vault,
+            &ctx.accounts.bettor,
+            &ctx.accounts.system_program,
+            token,
+            amount,
         )?;
+        require!(amount > 0, CustomError::InvalidAmount);
 
         // Agora atualizamos os dados on-chain
         let m = &mut ctx.accounts.match_account;
         let p = &mut ctx.accounts.participant;
 
-        // Como a conta é sempre nova (init), é só preencher os campos:
-        p.match_pubkey = m.key();
-        p.bettor = bettor_key;
-        p.side = side.clone();
-        p.amount = amount;
-        p.claimed = false;
+        if is_new_participant {
+            // Primeira aposta: preenche os campos
+            p.match_pubkey = m.key();
+            p.bettor = bettor_key;
+            p.side = side;
+            p.amount = amount;
+            p.claimed = false;
+
+            // Conta as participações abertas (a Match só fecha quando todas fecharem)
+            m.open_participants = m
+                .open_participants
+                .checked_add(1)
+                .ok_or(CustomError::MathOverflow)?;
+
+            // Conta os apostadores de cada lado (pra saber quando todos os
+            // vencedores já sacaram)
+            let side_participants = match side {
+                Side::PlayerA => &mut m.participants_side_a,
+                Side::PlayerB => &mut m.participants_side_b,
+            };
+            *side_participants = side_participants
+                .checked_add(1)
+                .ok_or(CustomError::MathOverflow)?;
+        } else {
+            // Aposta repetida no mesmo lado: acumula
+            p.amount = p
+                .amount
+                .checked_add(amount)
+                .ok_or(CustomError::MathOverflow)?;
+        }
 
         // Atualiza os totais da partida
         match side {
@@ -206,41 +592,229 @@ pub mod snake_betting {
             }
         }
 
+        emit_cpi!(BetPlaced {
+            match_pubkey: m.key(),
+            match_id: m.id,
+            bettor: bettor_key,
+            side,
+            amount,
+            bettor_total: p.amount,
+            total_side_a: m.total_side_a,
+            total_side_b: m.total_side_b,
+        });
+
+        Ok(())
+    }
+
+    /// Árbitro marca o início do jogo.
+    ///
+    /// A partida precisa estar `Funded` e vai pra `InProgress`: as apostas fecham
+    /// e o horário de início fica registrado em `started_at`. Só partidas em
+    /// andamento podem receber um resultado.
+    pub fn start_match(ctx: Context<ArbiterDecision>) -> Result<()> {
+        let m = &mut ctx.accounts.match_account;
+
+        // Só o árbitro pode iniciar
+        require!(ctx.accounts.arbiter.key() == m.arbiter, CustomError::NotArbiter);
+
+        // Os dois jogadores precisam ter depositado
+        require!(m.status != MatchStatus::Created, CustomError::MatchNotFunded);
+        require!(m.status == MatchStatus::Funded, CustomError::InvalidStatus);
+
+        // Depois do prazo de resolução a partida só pode ser expirada
+        let clock = Clock::get()?;
+        require!(clock.unix_timestamp < m.resolve_by, CustomError::ResolutionExpired);
+
+        m.status = MatchStatus::InProgress;
+        m.started_at = clock.unix_timestamp;
+
+        emit_cpi!(MatchStarted {
+            match_pubkey: m.key(),
+            match_id: m.id,
+            arbiter: ctx.accounts.arbiter.key(),
+            started_at: m.started_at,
+        });
+
         Ok(())
     }
 
-        /// Árbitro declara o vencedor (PlayerA ou PlayerB) após o deadline.
+    /// Árbitro declara o vencedor (PlayerA ou PlayerB) a partir de `earliest_resolution`.
+    ///
+    /// As taxas do protocolo e do árbitro são descontadas aqui, uma única vez,
+    /// do pote de stakes e do pool de apostas, e já são transferidas pra
+    /// tesouraria e pro árbitro.
     pub fn declare_winner(
         ctx: Context<DeclareWinner>,
         winner: Side,
     ) -> Result<()> {
+        let protocol_fee: u64;
+        let arbiter_fee: u64;
+        {
+            let m = &mut ctx.accounts.match_account;
+
+            // Só o árbitro pode declarar
+            require!(ctx.accounts.arbiter.key() == m.arbiter, CustomError::NotArbiter);
+
+            let clock = Clock::get()?;
+            m.check_resolvable(clock.unix_timestamp)?;
+
+            m.winner = Some(winner);
+            m.status = MatchStatus::Resolved;
+
+            // Daqui até `claim_deadline` os vencedores podem sacar
+            m.claim_deadline = clock
+                .unix_timestamp
+                .checked_add(m.claim_window)
+                .ok_or(CustomError::MathOverflow)?;
+
+            // Pausas e suspensões de saque só adiam o prazo a partir daqui
+            m.paused_secs_at_resolution =
+                ctx.accounts.config.withdrawals_paused_secs_at(clock.unix_timestamp);
+            if m.withdrawals_suspended {
+                m.withdrawals_suspended_at = clock.unix_timestamp;
+            }
+
+            m.apply_fees()?;
+            protocol_fee = m.protocol_fee;
+            arbiter_fee = m.arbiter_fee;
+        }
+
+        // Transferência das taxas: escrow da Match -> tesouraria / árbitro
+        if protocol_fee > 0 {
+            let token = token_escrow(
+                &ctx.accounts.match_account,
+                &ctx.accounts.mint,
+                &ctx.accounts.token_vault,
+                &ctx.accounts.treasury_token_account,
+                &ctx.accounts.token_program,
+                ctx.accounts.treasury.key(),
+            )?;
+            escrow_withdraw(
+                &mut ctx.accounts.match_account,
+                &ctx.accounts.vault,
+                &ctx.accounts.treasury,
+                &ctx.accounts.system_program,
+                token,
+                protocol_fee,
+            )?;
+        }
+        if arbiter_fee > 0 {
+            let token = token_escrow(
+                &ctx.accounts.match_account,
+                &ctx.accounts.mint,
+                &ctx.accounts.token_vault,
+                &ctx.accounts.arbiter_token_account,
+                &ctx.accounts.token_program,
+                ctx.accounts.arbiter.key(),
+            )?;
+            escrow_withdraw(
+                &mut ctx.accounts.match_account,
+                &ctx.accounts.vault,
+                &ctx.accounts.arbiter,
+                &ctx.accounts.system_program,
+                token,
+                arbiter_fee,
+            )?;
+        }
+
+        let m = &ctx.accounts.match_account;
+        emit_cpi!(WinnerDeclared {
+            match_pubkey: m.key(),
+            match_id: m.id,
+            arbiter: ctx.accounts.arbiter.key(),
+            winner,
+            protocol_fee,
+            arbiter_fee,
+            stake_pot: m.stake_pot,
+            payout_pool: m.payout_pool,
+            claim_deadline: m.claim_deadline,
+        });
+
+        Ok(())
+    }
+
+    /// Árbitro declara empate (ex: colisão de cabeça com cabeça) a partir de
+    /// `earliest_resolution`.
+    ///
+    /// Ninguém ganha: os jogadores recebem o stake de volta via `refund_player`
+    /// e os apostadores recebem a aposta de volta via `refund_bet`.
+    pub fn declare_draw(ctx: Context<ArbiterDecision>) -> Result<()> {
         let m = &mut ctx.accounts.match_account;
 
         // Só o árbitro pode declarar
         require!(ctx.accounts.arbiter.key() == m.arbiter, CustomError::NotArbiter);
 
-        // Precisa estar em estado válido
-        require!(
-            m.status == MatchStatus::Created
-                || m.status == MatchStatus::Funded
-                || m.status == MatchStatus::InProgress,
-            CustomError::InvalidStatus
-        );
+        let clock = Clock::get()?;
+        m.check_resolvable(clock.unix_timestamp)?;
 
-        // Já resolvida?
-        require!(m.winner.is_none(), CustomError::AlreadyResolved);
+        m.status = MatchStatus::Draw;
+
+        emit_cpi!(DrawDeclared {
+            match_pubkey: m.key(),
+            match_id: m.id,
+            arbiter: ctx.accounts.arbiter.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Árbitro declara W.O.: só um jogador depositou e o outro não apareceu
+    /// até o prazo de depósito (`fund_by`).
+    ///
+    /// O jogador que depositou vence e saca o próprio stake via
+    /// `withdraw_winner_stake`. Como o jogo não aconteceu, as apostas são
+    /// devolvidas via `refund_bet`.
+    pub fn declare_walkover(ctx: Context<ArbiterDecision>) -> Result<()> {
+        let m = &mut ctx.accounts.match_account;
+
+        // Só o árbitro pode declarar
+        require!(ctx.accounts.arbiter.key() == m.arbiter, CustomError::NotArbiter);
 
-        // Verificar deadline
         let clock = Clock::get()?;
-        require!(clock.unix_timestamp >= m.deadline, CustomError::TooEarly);
+        m.apply_walkover(clock.unix_timestamp)?;
+
+        emit_cpi!(WalkoverDeclared {
+            match_pubkey: m.key(),
+            match_id: m.id,
+            declared_by: ctx.accounts.arbiter.key(),
+            winner: m.winner.ok_or(CustomError::NoWinner)?,
+        });
+
+        Ok(())
+    }
+
+    /// Jogador que depositou reivindica a vitória por W.O. depois do prazo de depósito.
+    ///
+    /// Mesmo efeito da `declare_walkover`, mas sem depender do árbitro.
+    pub fn claim_walkover(ctx: Context<ClaimWalkover>) -> Result<()> {
+        let player_key = ctx.accounts.player.key();
+        let m = &mut ctx.accounts.match_account;
 
-        m.winner = Some(winner);
-        m.status = MatchStatus::Resolved;
+        let clock = Clock::get()?;
+        m.apply_walkover(clock.unix_timestamp)?;
+
+        // Quem reivindica precisa ser o jogador que depositou
+        let (winner, winner_key) = match m.winner {
+            Some(Side::PlayerA) => (Side::PlayerA, m.player_a),
+            Some(Side::PlayerB) => (Side::PlayerB, m.player_b),
+            None => return err!(CustomError::NoWinner),
+        };
+        require!(player_key == winner_key, CustomError::NotWinnerPlayer);
+
+        emit_cpi!(WalkoverDeclared {
+            match_pubkey: m.key(),
+            match_id: m.id,
+            declared_by: player_key,
+            winner,
+        });
 
         Ok(())
     }
 
-    /// Jogador vencedor saca os dois stakes (2 x stake_lamports).
+    /// Jogador vencedor saca os stakes depositados.
+    ///
+    /// Numa partida normal são os dois stakes (2 x stake_lamports) menos as taxas;
+    /// num W.O. é só o stake do próprio vencedor, que foi o único a depositar.
     pub fn withdraw_winner_stake(ctx: Context<WithdrawWinnerStake>) -> Result<()> {
         let winner_key = ctx.accounts.winner.key();
 
@@ -249,8 +823,11 @@ pub mod snake_betting {
         {
             let m = &ctx.accounts.match_account;
 
-            // A partida precisa estar resolvida
-            require!(m.status == MatchStatus::Resolved, CustomError::InvalidStatus);
+            // A partida precisa estar resolvida (com jogo ou por W.O.)
+            require!(
+                m.status == MatchStatus::Resolved || m.status == MatchStatus::Walkover,
+                CustomError::InvalidStatus
+            );
             let winner_side = m.winner.ok_or(CustomError::NoWinner)?;
 
             // Checar se o signer é o player vencedor
@@ -267,39 +844,64 @@ pub mod snake_betting {
             // Só pode sacar uma vez
             require!(!m.stakes_withdrawn, CustomError::StakesAlreadyWithdrawn);
 
-            // Valor total de stakes (2x)
-            stakes_total = m
-                .stake_lamports
-                .checked_mul(2)
-                .ok_or(CustomError::MathOverflow)?;
+            // Saques não podem estar pausados
+            m.check_withdrawals_open(&ctx.accounts.config)?;
+
+            // Só dentro do prazo de saque
+            require!(
+                m.claim_window_open(&ctx.accounts.config, Clock::get()?.unix_timestamp),
+                CustomError::ClaimWindowExpired
+            );
+
+            // Só paga o que foi realmente depositado, já sem as taxas
+            // (descontadas na `declare_winner` via `settlement::split_fees`)
+            stakes_total = m.stake_pot;
         }
 
-        // Transferência manual de lamports: Match -> winner
+        // Transferência: escrow da Match -> winner
         {
-            let match_info = ctx.accounts.match_account.to_account_info();
-            let winner_info = ctx.accounts.winner.to_account_info();
-
-            **match_info.try_borrow_mut_lamports()? = match_info
-                .lamports()
-                .checked_sub(stakes_total)
-                .ok_or(CustomError::MathOverflow)?;
-            **winner_info.try_borrow_mut_lamports()? = winner_info
-                .lamports()
-                .checked_add(stakes_total)
-                .ok_or(CustomError::MathOverflow)?;
+            let m = &ctx.accounts.match_account;
+            let token = token_escrow(
+                m,
+                &ctx.accounts.mint,
+                &ctx.accounts.token_vault,
+                &ctx.accounts.winner_token_account,
+                &ctx.accounts.token_program,
+                winner_key,
+            )?;
+            escrow_withdraw(
+                &mut ctx.accounts.match_account,
+                &ctx.accounts.vault,
+                &ctx.accounts.winner,
+                &ctx.accounts.system_program,
+                token,
+                stakes_total,
+            )?;
         }
 
         // Agora pegamos a match como mutável só pra atualizar o flag
         let m = &mut ctx.accounts.match_account;
         m.stakes_withdrawn = true;
+        m.stake_pot = 0;
+
+        emit_cpi!(WinnerStakeWithdrawn {
+            match_pubkey: m.key(),
+            match_id: m.id,
+            winner: winner_key,
+            amount: stakes_total,
+        });
 
         Ok(())
     }
 
     /// Apostador do lado vencedor saca sua parte do pool de apostas.
     ///
-    /// Aqui só distribuímos o pool de apostas (total_side_a + total_side_b).
+    /// Aqui só distribuímos o pool de apostas (total_side_a + total_side_b, menos
+    /// as taxas descontadas na `declare_winner`, ou seja, `payout_pool`).
     /// Os stakes dos jogadores são tratados na `withdraw_winner_stake`.
+    ///
+    /// Só até `claim_deadline`; depois disso o prêmio vai pra tesouraria
+    /// (`sweep_unclaimed`).
     pub fn claim_bet_payout(ctx: Context<ClaimBetPayout>) -> Result<()> {
         let bettor_key = ctx.accounts.bettor.key();
 
@@ -322,11 +924,14 @@ pub mod snake_betting {
             // Só pode sacar uma vez
             require!(!p.claimed, CustomError::AlreadyClaimed);
 
-            // Pool de apostas (não inclui stakes)
-            let pool_bets = m
-                .total_side_a
-                .checked_add(m.total_side_b)
-                .ok_or(CustomError::MathOverflow)?;
+            // Saques não podem estar pausados
+            m.check_withdrawals_open(&ctx.accounts.config)?;
+
+            // Só dentro do prazo de saque
+            require!(
+                m.claim_window_open(&ctx.accounts.config, Clock::get()?.unix_timestamp),
+                CustomError::ClaimWindowExpired
+            );
 
             // Total apostado no lado vencedor
             let winner_bets_total = match winner_side {
@@ -334,203 +939,2054 @@ pub mod snake_betting {
                 Side::PlayerB => m.total_side_b,
             };
 
-            // Não pode dividir por zero: precisa existir apostas no lado vencedor
-            require!(winner_bets_total > 0, CustomError::NoBetsOnWinnerSide);
-
-            // payout = amount * pool_bets / winner_bets_total
-            let payout = (p.amount as u128)
-                .checked_mul(pool_bets as u128)
-                .ok_or(CustomError::MathOverflow)?
-                / (winner_bets_total as u128);
-
-            payout_u64 = u64::try_from(payout).map_err(|_| CustomError::MathOverflow)?;
+            // payout = amount * payout_pool / winner_bets_total
+            // (pool de apostas já descontadas as taxas, não inclui stakes)
+            payout_u64 = settlement::bet_payout(p.amount, m.payout_pool, winner_bets_total)?;
         }
 
-        // Transferência manual de lamports: Match -> bettor
+        // Transferência: escrow da Match -> bettor
         {
-            let match_info = ctx.accounts.match_account.to_account_info();
-            let bettor_info = ctx.accounts.bettor.to_account_info();
-
-            **match_info.try_borrow_mut_lamports()? = match_info
-                .lamports()
-                .checked_sub(payout_u64)
-                .ok_or(CustomError::MathOverflow)?;
-            **bettor_info.try_borrow_mut_lamports()? = bettor_info
-                .lamports()
-                .checked_add(payout_u64)
-                .ok_or(CustomError::MathOverflow)?;
+            let m = &ctx.accounts.match_account;
+            let token = token_escrow(
+                m,
+                &ctx.accounts.mint,
+                &ctx.accounts.token_vault,
+                &ctx.accounts.bettor_token_account,
+                &ctx.accounts.token_program,
+                bettor_key,
+            )?;
+            escrow_withdraw(
+                &mut ctx.accounts.match_account,
+                &ctx.accounts.vault,
+                &ctx.accounts.bettor,
+                &ctx.accounts.system_program,
+                token,
+                payout_u64,
+            )?;
         }
 
         // Agora atualizamos a conta de participação para marcar como já sacada
         let p = &mut ctx.accounts.participant;
         p.claimed = true;
 
+        let m = &mut ctx.accounts.match_account;
+        m.payouts_claimed = m
+            .payouts_claimed
+            .checked_add(1)
+            .ok_or(CustomError::MathOverflow)?;
+
+        emit_cpi!(BetPayoutClaimed {
+            match_pubkey: m.key(),
+            match_id: m.id,
+            bettor: bettor_key,
+            side: p.side,
+            amount: payout_u64,
+        });
+
         Ok(())
     }
 
-}
+    /// Árbitro cancela a partida (ex: servidor do jogo caiu).
+    ///
+    /// Depois de cancelada, jogadores e apostadores recebem o dinheiro de volta
+    /// via `refund_player` e `refund_bet`.
+    pub fn cancel_match(ctx: Context<CancelMatch>) -> Result<()> {
+        let m = &mut ctx.accounts.match_account;
 
-/// Contexto vazio para a função initialize
-#[derive(Accounts)]
-pub struct Initialize {}
+        // Só o árbitro pode cancelar
+        require!(ctx.accounts.arbiter.key() == m.arbiter, CustomError::NotArbiter);
 
-/// Accounts da instrução create_match
-///
-/// Aqui a gente cria a conta `Match` como PDA.
-/// Ela mesma será o "vault" (escrow) que segura os SOL da partida.
-#[derive(Accounts)]
-pub struct CreateMatch<'info> {
-    /// Árbitro da partida: quem pode declarar o vencedor
-    #[account(mut)]
-    pub arbiter: Signer<'info>,
+        // Não dá pra cancelar partida já resolvida ou já cancelada
+        require!(
+            m.status == MatchStatus::Created
+                || m.status == MatchStatus::Funded
+                || m.status == MatchStatus::InProgress,
+            CustomError::InvalidStatus
+        );
 
-    /// Conta principal da partida (Match), criada como PDA
-    #[account(
-        init,
-        payer = arbiter,
-        space = Match::LEN,
-        // Cada árbitro, com essas seeds, tem uma match associada.
-        seeds = [b"match", arbiter.key().as_ref()],
-        bump
-    )]
-    pub match_account: Account<'info, Match>,
+        m.status = MatchStatus::Cancelled;
 
-    /// Programa do sistema (obrigatório pra criar contas)
-    pub system_program: Program<'info, System>,
-}
+        emit_cpi!(MatchCancelled {
+            match_pubkey: m.key(),
+            match_id: m.id,
+            arbiter: ctx.accounts.arbiter.key(),
+        });
 
-/// Accounts da instrução join_as_player
-///
-/// - `player`: precisa ser igual a `player_a` ou `player_b` salvos na Match.
-/// - `match_account`: mesma PDA da CreateMatch.
-/// - `system_program`: pra fazer o transfer de SOL.
-#[derive(Accounts)]
-pub struct JoinAsPlayer<'info> {
-    /// Jogador A ou B
-    #[account(mut)]
-    pub player: Signer<'info>,
+        Ok(())
+    }
 
-    /// Conta da partida (escrow), que vai receber o SOL
-    #[account(
-        mut,
-        seeds = [b"match", match_account.arbiter.as_ref()],
-        bump = match_account.bump
-    )]
-    pub match_account: Account<'info, Match>,
+    /// Expira uma partida que o árbitro não resolveu até `resolve_by`, ou que
+    /// não recebeu os dois stakes até `fund_by`.
+    ///
+    /// Qualquer um pode chamar. A partida vai pra `Cancelled` e jogadores e
+    /// apostadores recebem o dinheiro de volta via `refund_player` e `refund_bet`.
+    pub fn expire_match(ctx: Context<ExpireMatch>) -> Result<()> {
+        let m = &mut ctx.accounts.match_account;
 
-    /// Programa do sistema (obrigatório pro transfer)
-    pub system_program: Program<'info, System>,
-}
+        // Só partidas ainda não resolvidas/canceladas
+        require!(
+            m.status == MatchStatus::Created
+                || m.status == MatchStatus::Funded
+                || m.status == MatchStatus::InProgress,
+            CustomError::InvalidStatus
+        );
 
-/// Accounts da instrução place_bet
+        // Só depois do prazo de resolução (ou do prazo de depósito, se não foi financiada)
+        let clock = Clock::get()?;
+        let funding_expired =
+            m.status == MatchStatus::Created && clock.unix_timestamp >= m.fund_by;
+        require!(
+            funding_expired || clock.unix_timestamp >= m.resolve_by,
+            CustomError::ResolutionNotExpired
+        );
+
+        m.status = MatchStatus::Cancelled;
+
+        emit_cpi!(MatchExpired {
+            match_pubkey: m.key(),
+            match_id: m.id,
+            funding_expired,
+        });
+
+        Ok(())
+    }
+
+    /// Devolve o stake de um jogador numa partida cancelada ou empatada.
+    ///
+    /// Qualquer um pode chamar; o dinheiro sempre vai pra wallet do jogador.
+    pub fn refund_player(ctx: Context<RefundPlayer>) -> Result<()> {
+        let player_key = ctx.accounts.player.key();
+
+        let refund_amount: u64;
+        {
+            let m = &ctx.accounts.match_account;
+
+            // Só partidas canceladas ou empatadas têm reembolso de stake
+            require!(m.stakes_refundable(), CustomError::InvalidStatus);
+
+            // Saques não podem estar pausados
+            m.check_withdrawals_open(&ctx.accounts.config)?;
+
+            // Precisa ser player A ou B e ter depositado; devolve o que entrou no escrow
+            if player_key == m.player_a {
+                require!(m.player_a_deposited, CustomError::NothingToRefund);
+                refund_amount = m.player_a_stake;
+            } else if player_key == m.player_b {
+                require!(m.player_b_deposited, CustomError::NothingToRefund);
+                refund_amount = m.player_b_stake;
+            } else {
+                return err!(CustomError::NotAPlayer);
+            }
+        }
+
+        {
+            let m = &ctx.accounts.match_account;
+            let token = token_escrow(
+                m,
+                &ctx.accounts.mint,
+                &ctx.accounts.token_vault,
+                &ctx.accounts.player_token_account,
+                &ctx.accounts.token_program,
+                player_key,
+            )?;
+            escrow_withdraw(
+                &mut ctx.accounts.match_account,
+                &ctx.accounts.vault,
+                &ctx.accounts.player,
+                &ctx.accounts.system_program,
+                token,
+                refund_amount,
+            )?;
+        }
+
+        // Limpa o flag de depósito pra não reembolsar duas vezes
+        let m = &mut ctx.accounts.match_account;
+        if player_key == m.player_a {
+            m.player_a_deposited = false;
+            m.player_a_stake = 0;
+        } else {
+            m.player_b_deposited = false;
+            m.player_b_stake = 0;
+        }
+
+        m.stake_pot = m
+            .stake_pot
+            .checked_sub(refund_amount)
+            .ok_or(CustomError::MathOverflow)?;
+
+        emit_cpi!(PlayerRefunded {
+            match_pubkey: m.key(),
+            match_id: m.id,
+            player: player_key,
+            amount: refund_amount,
+            stake_pot: m.stake_pot,
+        });
+
+        Ok(())
+    }
+
+    /// Devolve a aposta de um apostador numa partida cancelada, empatada ou por W.O.
+    ///
+    /// Também devolve as apostas de uma partida resolvida em que ninguém apostou
+    /// no vencedor, se a política da partida for `RefundBettors`.
+    ///
+    /// Qualquer um pode chamar; o dinheiro sempre vai pra wallet do apostador.
+    pub fn refund_bet(ctx: Context<RefundBet>) -> Result<()> {
+        let refund_amount: u64;
+        {
+            let m = &ctx.accounts.match_account;
+            let p = &ctx.accounts.participant;
+
+            // Partidas canceladas, empatadas ou por W.O. devolvem as apostas.
+            // Numa partida resolvida sem apostas no vencedor, depende da política.
+            let refund_unmatched = m.pool_unmatched()
+                && m.unmatched_pool_policy == UnmatchedPoolPolicy::RefundBettors;
+            require!(
+                m.bets_refundable() || refund_unmatched,
+                CustomError::InvalidStatus
+            );
+
+            // Só pode ser reembolsado uma vez
+            require!(!p.claimed, CustomError::AlreadyClaimed);
+
+            // Saques não podem estar pausados
+            m.check_withdrawals_open(&ctx.accounts.config)?;
+
+            // Partida resolvida só reembolsa dentro do prazo de saque
+            require!(
+                m.claim_window_open(&ctx.accounts.config, Clock::get()?.unix_timestamp),
+                CustomError::ClaimWindowExpired
+            );
+
+            refund_amount = p.amount;
+        }
+
+        {
+            let m = &ctx.accounts.match_account;
+            let token = token_escrow(
+                m,
+                &ctx.accounts.mint,
+                &ctx.accounts.token_vault,
+                &ctx.accounts.bettor_token_account,
+                &ctx.accounts.token_program,
+                ctx.accounts.bettor.key(),
+            )?;
+            escrow_withdraw(
+                &mut ctx.accounts.match_account,
+                &ctx.accounts.vault,
+                &ctx.accounts.bettor,
+                &ctx.accounts.system_program,
+                token,
+                refund_amount,
+            )?;
+        }
+
+        let p = &mut ctx.accounts.participant;
+        p.claimed = true;
+
+        let m = &ctx.accounts.match_account;
+        emit_cpi!(BetRefunded {
+            match_pubkey: m.key(),
+            match_id: m.id,
+            bettor: ctx.accounts.bettor.key(),
+            side: p.side,
+            amount: refund_amount,
+        });
+
+        Ok(())
+    }
+
+    /// Manda o pool de apostas pro jogador vencedor ou pra tesouraria quando
+    /// ninguém apostou no vencedor.
+    ///
+    /// Só vale pra partidas com política `WinningPlayer` ou `Treasury`. Qualquer um
+    /// pode chamar; o dinheiro sempre vai pro destino definido pela política.
+    pub fn claim_unmatched_pool(ctx: Context<ClaimUnmatchedPool>) -> Result<()> {
+        let pool_bets: u64;
+        {
+            let m = &ctx.accounts.match_account;
+
+            // Partida resolvida sem nenhuma aposta no lado vencedor
+            require!(m.pool_unmatched(), CustomError::PoolNotUnmatched);
+
+            // Só pode sacar uma vez
+            require!(!m.unmatched_pool_claimed, CustomError::AlreadyClaimed);
+
+            // Saques não podem estar pausados
+            m.check_withdrawals_open(&ctx.accounts.config)?;
+
+            // Só dentro do prazo de saque
+            require!(
+                m.claim_window_open(&ctx.accounts.config, Clock::get()?.unix_timestamp),
+                CustomError::ClaimWindowExpired
+            );
+
+            // O destino depende da política da partida
+            let recipient_key = m.unmatched_pool_recipient(ctx.accounts.config.treasury)?;
+            require!(
+                ctx.accounts.recipient.key() == recipient_key,
+                CustomError::WrongRecipient
+            );
+
+            // Pool de apostas já descontadas as taxas
+            pool_bets = m.payout_pool;
+        }
+
+        {
+            let m = &ctx.accounts.match_account;
+            let token = token_escrow(
+                m,
+                &ctx.accounts.mint,
+                &ctx.accounts.token_vault,
+                &ctx.accounts.recipient_token_account,
+                &ctx.accounts.token_program,
+                ctx.accounts.recipient.key(),
+            )?;
+            escrow_withdraw(
+                &mut ctx.accounts.match_account,
+                &ctx.accounts.vault,
+                &ctx.accounts.recipient,
+                &ctx.accounts.system_program,
+                token,
+                pool_bets,
+            )?;
+        }
+
+        let m = &mut ctx.accounts.match_account;
+        m.unmatched_pool_claimed = true;
+
+        emit_cpi!(UnmatchedPoolClaimed {
+            match_pubkey: m.key(),
+            match_id: m.id,
+            recipient: ctx.accounts.recipient.key(),
+            amount: pool_bets,
+        });
+
+        Ok(())
+    }
+
+    /// Manda a sobra de arredondamento do pool de apostas pro jogador vencedor
+    /// ou pra tesouraria, depois que todos os apostadores vencedores sacaram.
+    ///
+    /// Cada `claim_bet_payout` arredonda o prêmio pra baixo, então sobram algumas
+    /// unidades no escrow que não são de ninguém. O destino segue a política da
+    /// partida: com `WinningPlayer` vai pro jogador vencedor; com `Treasury` ou
+    /// `RefundBettors` (não tem apostador pra reembolsar) vai pra tesouraria.
+    ///
+    /// Qualquer um pode chamar. A Match só pode ser fechada depois da varredura.
+    pub fn sweep_dust(ctx: Context<SweepDust>) -> Result<()> {
+        let dust: u64;
+        {
+            let m = &ctx.accounts.match_account;
+
+            // Partida resolvida com apostas no lado vencedor
+            require!(m.status == MatchStatus::Resolved, CustomError::InvalidStatus);
+            require!(m.winner_bets_total() > 0, CustomError::NoBetsOnWinnerSide);
+
+            // Todos os vencedores precisam ter sacado
+            require!(
+                m.payouts_claimed == m.winner_participants(),
+                CustomError::PayoutsPending
+            );
+
+            // Só pode varrer uma vez (o `sweep_unclaimed` já leva a sobra junto)
+            require!(!m.dust_swept && !m.unclaimed_swept, CustomError::AlreadyClaimed);
+
+            // Saques não podem estar pausados
+            m.check_withdrawals_open(&ctx.accounts.config)?;
+
+            require!(
+                ctx.accounts.recipient.key() == m.dust_recipient(ctx.accounts.config.treasury)?,
+                CustomError::WrongRecipient
+            );
+
+            dust = m.pool_dust()?;
+        }
+
+        if dust > 0 {
+            let m = &ctx.accounts.match_account;
+            let token = token_escrow(
+                m,
+                &ctx.accounts.mint,
+                &ctx.accounts.token_vault,
+                &ctx.accounts.recipient_token_account,
+                &ctx.accounts.token_program,
+                ctx.accounts.recipient.key(),
+            )?;
+            escrow_withdraw(
+                &mut ctx.accounts.match_account,
+                &ctx.accounts.vault,
+                &ctx.accounts.recipient,
+                &ctx.accounts.system_program,
+                token,
+                dust,
+            )?;
+        }
+
+        let m = &mut ctx.accounts.match_account;
+        m.dust_swept = true;
+
+        emit_cpi!(DustSwept {
+            match_pubkey: m.key(),
+            match_id: m.id,
+            recipient: ctx.accounts.recipient.key(),
+            amount: dust,
+        });
+
+        Ok(())
+    }
+
+    /// Manda pra tesouraria tudo que os vencedores não sacaram até `claim_deadline`.
+    ///
+    /// Só o árbitro da partida ou o admin do protocolo podem chamar. Depois da
+    /// varredura, as participações ainda abertas podem ser fechadas (o rent
+    /// volta pro apostador) e a Match pode ser fechada.
+    pub fn sweep_unclaimed(ctx: Context<SweepUnclaimed>) -> Result<()> {
+        let unclaimed: u64;
+        {
+            let m = &ctx.accounts.match_account;
+
+            // Só o árbitro ou o admin
+            let authority = ctx.accounts.authority.key();
+            require!(
+                authority == m.arbiter || authority == ctx.accounts.config.admin,
+                CustomError::NotMatchAuthority
+            );
+
+            // Partida resolvida com o prazo de saque vencido
+            require!(m.status == MatchStatus::Resolved, CustomError::InvalidStatus);
+            require!(
+                !m.claim_window_open(&ctx.accounts.config, Clock::get()?.unix_timestamp),
+                CustomError::ClaimWindowOpen
+            );
+
+            // Só pode varrer uma vez
+            require!(!m.unclaimed_swept, CustomError::AlreadyClaimed);
+
+            // Saques não podem estar pausados
+            m.check_withdrawals_open(&ctx.accounts.config)?;
+
+            // Tudo que a partida ainda deve: stakes, prêmios, pool e sobra
+            unclaimed = m.escrowed;
+        }
+
+        if unclaimed > 0 {
+            let m = &ctx.accounts.match_account;
+            let token = token_escrow(
+                m,
+                &ctx.accounts.mint,
+                &ctx.accounts.token_vault,
+                &ctx.accounts.treasury_token_account,
+                &ctx.accounts.token_program,
+                ctx.accounts.treasury.key(),
+            )?;
+            escrow_withdraw(
+                &mut ctx.accounts.match_account,
+                &ctx.accounts.vault,
+                &ctx.accounts.treasury,
+                &ctx.accounts.system_program,
+                token,
+                unclaimed,
+            )?;
+        }
+
+        let m = &mut ctx.accounts.match_account;
+        m.unclaimed_swept = true;
+        m.stake_pot = 0;
+
+        emit_cpi!(UnclaimedSwept {
+            match_pubkey: m.key(),
+            match_id: m.id,
+            authority: ctx.accounts.authority.key(),
+            treasury: ctx.accounts.treasury.key(),
+            amount: unclaimed,
+        });
+
+        Ok(())
+    }
+
+    /// Fecha a conta de participação de um apostador já liquidado.
+    ///
+    /// Qualquer um pode chamar; o rent volta sempre pro apostador que pagou.
+    /// Vale pra quem já sacou/foi reembolsado e pra quem apostou no lado perdedor.
+    pub fn close_participant(ctx: Context<CloseParticipant>) -> Result<()> {
+        let m = &mut ctx.accounts.match_account;
+        let p = &ctx.accounts.participant;
+
+        require!(p.is_settled(m), CustomError::ParticipantNotSettled);
+
+        m.open_participants = m
+            .open_participants
+            .checked_sub(1)
+            .ok_or(CustomError::MathOverflow)?;
+
+        emit_cpi!(ParticipantClosed {
+            match_pubkey: m.key(),
+            match_id: m.id,
+            bettor: ctx.accounts.bettor.key(),
+            open_participants: m.open_participants,
+        });
+
+        // O fechamento em si (e a devolução do rent) é feito pelo `close = bettor`
+        Ok(())
+    }
+
+    /// Fecha a Match depois que todos os stakes e apostas foram liquidados.
+    ///
+    /// Qualquer um pode chamar; o rent volta pro árbitro, inclusive o do vault
+    /// de lamports. A sobra de arredondamento já saiu no `sweep_dust`; o que
+    /// ainda estiver no vault (ex: mandado direto pra ele) também vai pro árbitro.
+    /// Em partidas SPL, o vault de tokens também é fechado (antes, as taxas de
+    /// transferência retidas nele vão pro mint).
+    pub fn close_match(ctx: Context<CloseMatch>) -> Result<()> {
+        let m = &ctx.accounts.match_account;
+
+        require!(m.is_settled(), CustomError::MatchNotSettled);
+
+        let token = token_escrow(
+            m,
+            &ctx.accounts.mint,
+            &ctx.accounts.token_vault,
+            &ctx.accounts.arbiter_token_account,
+            &ctx.accounts.token_program,
+            ctx.accounts.arbiter.key(),
+        )?;
+
+        if let Some(token) = token {
+            // Saldo que não é obrigação de ninguém vai pro árbitro
+            let leftover = token.vault.amount;
+            let vault = token.vault.to_account_info();
+            let token_program = token.token_program.to_account_info();
+            if leftover > 0 {
+                escrow_transfer(
+                    m,
+                    &ctx.accounts.vault,
+                    &ctx.accounts.arbiter,
+                    &ctx.accounts.system_program,
+                    Some(&token),
+                    leftover,
+                )?;
+            }
+
+            let id_bytes = m.id.to_le_bytes();
+            let bump = [m.bump];
+            let signer_seeds: &[&[&[u8]]] =
+                &[&[b"match", m.arbiter.as_ref(), &id_bytes, &bump]];
+
+            // Num mint Token-2022 com taxa de transferência, o vault acumula as
+            // taxas retidas e não fecha com elas: manda tudo pro mint antes
+            let mint = token.mint.to_account_info();
+            if token_interface::get_mint_extension_data::<TransferFeeConfig>(&mint).is_ok() {
+                token_interface::harvest_withheld_tokens_to_mint(
+                    CpiContext::new(
+                        token_program.clone(),
+                        HarvestWithheldTokensToMint {
+                            token_program_id: token_program.clone(),
+                            mint,
+                        },
+                    ),
+                    vec![vault.clone()],
+                )?;
+            }
+
+            token_interface::close_account(CpiContext::new_with_signer(
+                token_program,
+                CloseAccount {
+                    account: vault,
+                    destination: ctx.accounts.arbiter.to_account_info(),
+                    authority: m.to_account_info(),
+                },
+                signer_seeds,
+            ))?;
+        }
+
+        // O vault de lamports (rent + qualquer saldo extra) é esvaziado pro
+        // árbitro; com saldo zero a conta deixa de existir
+        let vault_lamports = ctx.accounts.vault.lamports();
+        transfer_from_vault(
+            m,
+            &ctx.accounts.vault,
+            &ctx.accounts.arbiter,
+            &ctx.accounts.system_program,
+            vault_lamports,
+        )?;
+
+        emit_cpi!(MatchClosed {
+            match_pubkey: m.key(),
+            match_id: m.id,
+            arbiter: ctx.accounts.arbiter.key(),
+        });
+
+        // O fechamento da Match em si é feito pelo `close = arbiter`
+        Ok(())
+    }
+
+}
+
+/// Contas de token usadas numa movimentação do escrow (só em partidas SPL).
+pub struct TokenEscrow<'a, 'info> {
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+}
+
+/// Monta (e valida) as contas de token de uma movimentação do escrow.
+///
+/// Em partidas em SOL devolve `None` e as contas de token são ignoradas.
+/// Em partidas SPL todas são obrigatórias, o mint precisa ser o da partida e
+/// a conta de token do usuário precisa ser desse mint e pertencer a `owner`.
+fn token_escrow<'a, 'info>(
+    m: &Match,
+    mint: &'a Option<InterfaceAccount<'info, Mint>>,
+    vault: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+    token_account: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+    token_program: &'a Option<Interface<'info, TokenInterface>>,
+    owner: Pubkey,
+) -> Result<Option<TokenEscrow<'a, 'info>>> {
+    let Some(match_mint) = m.mint else {
+        return Ok(None);
+    };
+
+    let (Some(mint), Some(vault), Some(token_account), Some(token_program)) = (
+        mint.as_ref(),
+        vault.as_ref(),
+        token_account.as_ref(),
+        token_program.as_ref(),
+    ) else {
+        return err!(CustomError::MissingTokenAccounts);
+    };
+
+    require!(mint.key() == match_mint, CustomError::InvalidTokenAccount);
+    require!(
+        token_account.mint == match_mint && token_account.owner == owner,
+        CustomError::InvalidTokenAccount
+    );
+
+    Ok(Some(TokenEscrow {
+        mint,
+        vault,
+        token_account,
+        token_program,
+    }))
+}
+
+/// Depósito no escrow da partida: SOL vai pro vault de lamports, token vai
+/// pro vault de tokens.
+///
+/// Devolve quanto o escrow realmente recebeu. Em SOL e SPL Token é o próprio
+/// `amount`; num mint Token-2022 com taxa de transferência é menos que isso.
+/// O valor recebido passa a contar em `escrowed`.
+fn escrow_deposit<'info>(
+    m: &mut Account<'info, Match>,
+    vault: &SystemAccount<'info>,
+    from: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    token: Option<TokenEscrow<'_, 'info>>,
+    amount: u64,
+) -> Result<u64> {
+    let received = match &token {
+        Some(token) => {
+            let balance_before = token.vault.amount;
+
+            token_interface::transfer_checked(
+                CpiContext::new(
+                    token.token_program.to_account_info(),
+                    TransferChecked {
+                        from: token.token_account.to_account_info(),
+                        mint: token.mint.to_account_info(),
+                        to: token.vault.to_account_info(),
+                        authority: from.to_account_info(),
+                    },
+                ),
+                amount,
+                token.mint.decimals,
+            )?;
+
+            // Relê o saldo do vault depois da CPI pra saber quanto entrou de fato
+            token_balance(token.vault)?
+                .checked_sub(balance_before)
+                .ok_or(CustomError::MathOverflow)?
+        }
+        None => {
+            let ix = anchor_lang::solana_program::system_instruction::transfer(
+                &from.key(),
+                &vault.key(),
+                amount,
+            );
+
+            anchor_lang::solana_program::program::invoke(
+                &ix,
+                &[
+                    from.to_account_info(),
+                    vault.to_account_info(),
+                    system_program.to_account_info(),
+                ],
+            )?;
+
+            amount
+        }
+    };
+
+    m.escrowed = m
+        .escrowed
+        .checked_add(received)
+        .ok_or(CustomError::MathOverflow)?;
+    check_escrow(m, vault, token.as_ref())?;
+
+    Ok(received)
+}
+
+/// Saque do escrow da partida pro destino (SOL ou token).
+///
+/// O valor sai de `escrowed`, e depois do saque o vault ainda precisa cobrir
+/// tudo que a partida deve. Se o mint cobrar taxa de transferência, ela é
+/// descontada do que o destino recebe.
+fn escrow_withdraw<'info>(
+    m: &mut Account<'info, Match>,
+    vault: &SystemAccount<'info>,
+    to: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    token: Option<TokenEscrow<'_, 'info>>,
+    amount: u64,
+) -> Result<()> {
+    escrow_transfer(m, vault, to, system_program, token.as_ref(), amount)?;
+
+    // Nunca dá pra pagar mais do que a partida deve
+    m.escrowed = m
+        .escrowed
+        .checked_sub(amount)
+        .ok_or(CustomError::EscrowInvariantViolated)?;
+    check_escrow(m, vault, token.as_ref())
+}
+
+/// Transferência crua do escrow pro destino, sem mexer na contabilidade.
+///
+/// No caso de token, quem assina a transferência do vault é a PDA da Match;
+/// no caso de SOL, é a PDA do vault de lamports.
+fn escrow_transfer<'info>(
+    m: &Account<'info, Match>,
+    vault: &SystemAccount<'info>,
+    to: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    token: Option<&TokenEscrow<'_, 'info>>,
+    amount: u64,
+) -> Result<()> {
+    match token {
+        Some(token) => {
+            let id_bytes = m.id.to_le_bytes();
+            let bump = [m.bump];
+            let signer_seeds: &[&[&[u8]]] =
+                &[&[b"match", m.arbiter.as_ref(), &id_bytes, &bump]];
+
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    token.token_program.to_account_info(),
+                    TransferChecked {
+                        from: token.vault.to_account_info(),
+                        mint: token.mint.to_account_info(),
+                        to: token.token_account.to_account_info(),
+                        authority: m.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount,
+                token.mint.decimals,
+            )
+        }
+        None => transfer_from_vault(m, vault, to, system_program, amount),
+    }
+}
+
+/// Transferência de lamports: vault da partida -> destino.
+///
+/// O vault é uma conta do System Program, então o saque é um transfer
+/// assinado pela PDA do vault.
+fn transfer_from_vault<'info>(
+    m: &Account<'info, Match>,
+    vault: &SystemAccount<'info>,
+    to: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    amount: u64,
+) -> Result<()> {
+    let match_key = m.key();
+    let bump = [m.vault_bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[b"vault", match_key.as_ref(), &bump]];
+
+    let ix = anchor_lang::solana_program::system_instruction::transfer(
+        &vault.key(),
+        &to.key(),
+        amount,
+    );
+
+    anchor_lang::solana_program::program::invoke_signed(
+        &ix,
+        &[
+            vault.to_account_info(),
+            to.clone(),
+            system_program.to_account_info(),
+        ],
+        signer_seeds,
+    )?;
+
+    Ok(())
+}
+
+/// Saldo atual de um vault de tokens, relido da conta (vale depois de uma CPI).
+fn token_balance(vault: &InterfaceAccount<'_, TokenAccount>) -> Result<u64> {
+    let vault_info = vault.to_account_info();
+    let data = vault_info.try_borrow_data()?;
+
+    Ok(TokenAccount::try_deserialize(&mut &data[..])?.amount)
+}
+
+/// Invariante contábil do escrow: o vault cobre tudo que a partida deve.
+///
+/// Em SOL, o vault de lamports tem o rent mínimo mais `escrowed`; em SPL, o
+/// vault de tokens tem `escrowed` e o de lamports só o rent. A comparação é
+/// `>=` porque qualquer um pode mandar lamports ou tokens direto pro vault;
+/// essa sobra não é obrigação de ninguém e vai pro árbitro no `close_match`.
+fn check_escrow(
+    m: &Match,
+    vault: &SystemAccount<'_>,
+    token: Option<&TokenEscrow<'_, '_>>,
+) -> Result<()> {
+    let rent_floor = Rent::get()?.minimum_balance(0);
+    let lamports_owed = if m.mint.is_none() { m.escrowed } else { 0 };
+
+    require!(
+        vault.lamports()
+            >= rent_floor
+                .checked_add(lamports_owed)
+                .ok_or(CustomError::MathOverflow)?,
+        CustomError::EscrowInvariantViolated
+    );
+
+    if let Some(token) = token {
+        require!(
+            token_balance(token.vault)? >= m.escrowed,
+            CustomError::EscrowInvariantViolated
+        );
+    }
+
+    Ok(())
+}
+
+/// Accounts da instrução initialize
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    /// Quem cria a configuração vira o admin do protocolo (paga o rent)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Configuração global do protocolo (singleton)
+    #[account(
+        init,
+        payer = admin,
+        space = Config::LEN,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Programa do sistema (obrigatório pra criar contas)
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts das instruções update_config, set_admin e set_paused
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    /// Admin atual do protocolo
+    pub admin: Signer<'info>,
+
+    /// Configuração global do protocolo
+    #[account(mut, seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+/// Accounts da instrução set_match_suspended
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetMatchSuspended<'info> {
+    /// Árbitro da partida ou admin do protocolo
+    pub authority: Signer<'info>,
+
+    /// Configuração global (fornece o admin)
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Match a ser suspensa/retomada
+    #[account(
+        mut,
+        seeds = [
+            b"match",
+            match_account.arbiter.as_ref(),
+            &match_account.id.to_le_bytes()
+        ],
+        bump = match_account.bump
+    )]
+    pub match_account: Account<'info, Match>,
+}
+
+/// Accounts da instrução init_match_counter
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitMatchCounter<'info> {
+    /// Árbitro dono do contador (paga o rent)
+    #[account(mut)]
+    pub arbiter: Signer<'info>,
+
+    /// Contador de partidas do árbitro, criado uma única vez
+    #[account(
+        init,
+        payer = arbiter,
+        space = MatchCounter::LEN,
+        seeds = [b"match_counter", arbiter.key().as_ref()],
+        bump
+    )]
+    pub match_counter: Account<'info, MatchCounter>,
+
+    /// Programa do sistema (obrigatório pra criar contas)
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts da instrução create_match
+///
+/// Aqui a gente cria a conta `Match` como PDA. Ela só guarda o estado:
+/// o SOL da partida fica num `vault` separado (PDA do System Program) e,
+/// em partidas SPL, os tokens ficam num `token_vault` controlado pela Match.
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(args: CreateMatchArgs)]
+pub struct CreateMatch<'info> {
+    /// Árbitro da partida: quem pode declarar o vencedor
+    #[account(mut)]
+    pub arbiter: Signer<'info>,
+
+    /// Configuração global (limites de stake e duração, pausa do protocolo)
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Contador de partidas do árbitro (fornece o id da nova match)
+    #[account(
+        mut,
+        seeds = [b"match_counter", arbiter.key().as_ref()],
+        bump = match_counter.bump
+    )]
+    pub match_counter: Account<'info, MatchCounter>,
+
+    /// Conta principal da partida (Match), criada como PDA
+    #[account(
+        init,
+        payer = arbiter,
+        space = Match::LEN,
+        // Seeds incluem o id, então um mesmo árbitro pode ter várias matches abertas.
+        seeds = [b"match", arbiter.key().as_ref(), &args.id.to_le_bytes()],
+        bump
+    )]
+    pub match_account: Account<'info, Match>,
+
+    /// Vault de lamports da partida (PDA do System Program), criado com o rent mínimo
+    #[account(
+        mut,
+        seeds = [b"vault", match_account.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Mint da partida (SPL Token ou Token-2022); se não for passado, a partida é em SOL nativo
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault de tokens da partida, controlado pela PDA da Match (só em partidas SPL)
+    #[account(
+        init,
+        payer = arbiter,
+        seeds = [b"token_vault", match_account.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = match_account,
+        token::token_program = token_program
+    )]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Programa de tokens, SPL Token ou Token-2022 (só em partidas SPL)
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Programa do sistema (obrigatório pra criar contas)
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts da instrução join_as_player
+///
+/// - `player`: precisa ser igual a `player_a` ou `player_b` salvos na Match.
+/// - `match_account`: mesma PDA da CreateMatch.
+/// - `vault`: vault de lamports da partida, que recebe o SOL.
+/// - `system_program`: pra fazer o transfer de SOL.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct JoinAsPlayer<'info> {
+    /// Jogador A ou B
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// Configuração global (pausa do protocolo)
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Conta da partida
+    #[account(
+        mut,
+        seeds = [
+            b"match",
+            match_account.arbiter.as_ref(),
+            &match_account.id.to_le_bytes()
+        ],
+        bump = match_account.bump
+    )]
+    pub match_account: Account<'info, Match>,
+
+    /// Vault de lamports da partida: guarda o SOL do escrow (e só isso)
+    #[account(
+        mut,
+        seeds = [b"vault", match_account.key().as_ref()],
+        bump = match_account.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Mint da partida (só em partidas SPL)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault de tokens da partida (só em partidas SPL)
+    #[account(
+        mut,
+        seeds = [b"token_vault", match_account.key().as_ref()],
+        bump
+    )]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Conta de token do jogador, de onde sai o stake (só em partidas SPL)
+    #[account(mut)]
+    pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Programa de tokens, SPL Token ou Token-2022 (só em partidas SPL)
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Programa do sistema (obrigatório pro transfer)
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts da instrução place_bet
+///
+/// - `bettor`: apostador (qualquer wallet pode apostar).
+/// - `match_account`: partida onde ele está apostando.
+/// - `participant`: conta que registra a aposta desse bettor nessa partida.
+///   - se não existir, é criada (init_if_needed)
+///   - se existir, é atualizada (a aposta acumula)
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PlaceBet<'info> {
+    /// Apostador (assinante da transação)
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    /// Configuração global (aposta mínima e pausa do protocolo)
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Conta da partida
+    #[account(
+        mut,
+        seeds = [
+            b"match",
+            match_account.arbiter.as_ref(),
+            &match_account.id.to_le_bytes()
+        ],
+        bump = match_account.bump
+    )]
+    pub match_account: Account<'info, Match>,
+
+    /// Vault de lamports da partida: guarda o SOL do escrow (e só isso)
+    #[account(
+        mut,
+        seeds = [b"vault", match_account.key().as_ref()],
+        bump = match_account.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Conta de participação do apostador nessa partida.
+    /// Criada na primeira aposta e reaproveitada nas seguintes.
+    #[account(
+        init_if_needed,
+        payer = bettor,
+        space = Participant::LEN,
+        seeds = [b"participant", match_account.key().as_ref(), bettor.key().as_ref()],
+        bump
+    )]
+    pub participant: Account<'info, Participant>,
+
+    /// Mint da partida (só em partidas SPL)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault de tokens da partida (só em partidas SPL)
+    #[account(
+        mut,
+        seeds = [b"token_vault", match_account.key().as_ref()],
+        bump
+    )]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Conta de token do apostador, de onde sai a aposta (só em partidas SPL)
+    #[account(mut)]
+    pub bettor_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Programa de tokens, SPL Token ou Token-2022 (só em partidas SPL)
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Programa do sistema (para criar conta e transferir SOL)
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts da instrução declare_winner
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DeclareWinner<'info> {
+    /// Árbitro que está declarando o resultado (recebe a taxa do árbitro)
+    #[account(mut)]
+    pub arbiter: Signer<'info>,
+
+    /// Configuração global (fornece a tesouraria)
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: só recebe lamports; precisa ser a tesouraria da Config
+    #[account(mut, address = config.treasury @ CustomError::WrongRecipient)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// Match a ser atualizada
+    #[account(
+        mut,
+        seeds = [
+            b"match",
+            match_account.arbiter.as_ref(),
+            &match_account.id.to_le_bytes()
+        ],
+        bump = match_account.bump
+    )]
+    pub match_account: Account<'info, Match>,
+
+    /// Vault de lamports da partida: guarda o SOL do escrow (e só isso)
+    #[account(
+        mut,
+        seeds = [b"vault", match_account.key().as_ref()],
+        bump = match_account.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Mint da partida (só em partidas SPL)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault de tokens da partida (só em partidas SPL)
+    #[account(
+        mut,
+        seeds = [b"token_vault", match_account.key().as_ref()],
+        bump
+    )]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Conta de token da tesouraria, que recebe a taxa do protocolo (só em partidas SPL)
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Conta de token do árbitro, que recebe a taxa do árbitro (só em partidas SPL)
+    #[account(mut)]
+    pub arbiter_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Programa de tokens, SPL Token ou Token-2022 (só em partidas SPL)
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Programa do sistema (pra transferir SOL do vault)
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts das instruções start_match, declare_draw e declare_walkover
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ArbiterDecision<'info> {
+    /// Árbitro que está iniciando a partida ou declarando o resultado
+    pub arbiter: Signer<'info>,
+
+    /// Match a ser atualizada
+    #[account(
+        mut,
+        seeds = [
+            b"match",
+            match_account.arbiter.as_ref(),
+            &match_account.id.to_le_bytes()
+        ],
+        bump = match_account.bump
+    )]
+    pub match_account: Account<'info, Match>,
+}
+
+/// Accounts da instrução withdraw_winner_stake
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawWinnerStake<'info> {
+    /// Jogador vencedor (PlayerA ou PlayerB)
+    #[account(mut)]
+    pub winner: Signer<'info>,
+
+    /// Configuração global (pausa do protocolo)
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Match resolvida (ou por W.O.)
+    #[account(
+        mut,
+        seeds = [
+            b"match",
+            match_account.arbiter.as_ref(),
+            &match_account.id.to_le_bytes()
+        ],
+        bump = match_account.bump
+    )]
+    pub match_account: Account<'info, Match>,
+
+    /// Vault de lamports da partida: guarda o SOL do escrow (e só isso)
+    #[account(
+        mut,
+        seeds = [b"vault", match_account.key().as_ref()],
+        bump = match_account.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Mint da partida (só em partidas SPL)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault de tokens da partida (só em partidas SPL)
+    #[account(
+        mut,
+        seeds = [b"token_vault", match_account.key().as_ref()],
+        bump
+    )]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Conta de token do vencedor, que recebe os stakes (só em partidas SPL)
+    #[account(mut)]
+    pub winner_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Programa de tokens, SPL Token ou Token-2022 (só em partidas SPL)
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Programa do sistema (pra transferir SOL do vault)
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts da instrução claim_bet_payout
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimBetPayout<'info> {
+    /// Apostador que está sacando
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    /// Configuração global (pausa do protocolo)
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Match resolvida
+    #[account(
+        mut,
+        seeds = [
+            b"match",
+            match_account.arbiter.as_ref(),
+            &match_account.id.to_le_bytes()
+        ],
+        bump = match_account.bump
+    )]
+    pub match_account: Account<'info, Match>,
+
+    /// Vault de lamports da partida: guarda o SOL do escrow (e só isso)
+    #[account(
+        mut,
+        seeds = [b"vault", match_account.key().as_ref()],
+        bump = match_account.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Conta de participação do apostador
+    #[account(
+        mut,
+        seeds = [b"participant", match_account.key().as_ref(), bettor.key().as_ref()],
+        bump
+    )]
+    pub participant: Account<'info, Participant>,
+
+    /// Mint da partida (só em partidas SPL)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault de tokens da partida (só em partidas SPL)
+    #[account(
+        mut,
+        seeds = [b"token_vault", match_account.key().as_ref()],
+        bump
+    )]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Conta de token do apostador, que recebe o prêmio (só em partidas SPL)
+    #[account(mut)]
+    pub bettor_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Programa de tokens, SPL Token ou Token-2022 (só em partidas SPL)
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Programa do sistema (pra transferir SOL do vault)
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts da instrução cancel_match
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelMatch<'info> {
+    /// Árbitro que está cancelando a partida
+    pub arbiter: Signer<'info>,
+
+    /// Match a ser cancelada
+    #[account(
+        mut,
+        seeds = [
+            b"match",
+            match_account.arbiter.as_ref(),
+            &match_account.id.to_le_bytes()
+        ],
+        bump = match_account.bump
+    )]
+    pub match_account: Account<'info, Match>,
+}
+
+/// Accounts da instrução claim_walkover
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimWalkover<'info> {
+    /// Jogador que depositou o stake
+    pub player: Signer<'info>,
+
+    /// Match que não foi financiada pelo outro jogador
+    #[account(
+        mut,
+        seeds = [
+            b"match",
+            match_account.arbiter.as_ref(),
+            &match_account.id.to_le_bytes()
+        ],
+        bump = match_account.bump
+    )]
+    pub match_account: Account<'info, Match>,
+}
+
+/// Accounts da instrução expire_match
+///
+/// Não precisa de signer específico: qualquer um pode expirar depois do prazo.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExpireMatch<'info> {
+    /// Match que passou do prazo de resolução
+    #[account(
+        mut,
+        seeds = [
+            b"match",
+            match_account.arbiter.as_ref(),
+            &match_account.id.to_le_bytes()
+        ],
+        bump = match_account.bump
+    )]
+    pub match_account: Account<'info, Match>,
+}
+
+/// Accounts da instrução refund_player
+///
+/// Não precisa de signer específico: o reembolso sempre vai pro próprio jogador.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RefundPlayer<'info> {
+    /// Wallet do jogador que recebe o stake de volta
+    #[account(mut)]
+    pub player: SystemAccount<'info>,
+
+    /// Configuração global (pausa do protocolo)
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Match cancelada/empatada
+    #[account(
+        mut,
+        seeds = [
+            b"match",
+            match_account.arbiter.as_ref(),
+            &match_account.id.to_le_bytes()
+        ],
+        bump = match_account.bump
+    )]
+    pub match_account: Account<'info, Match>,
+
+    /// Vault de lamports da partida: guarda o SOL do escrow (e só isso)
+    #[account(
+        mut,
+        seeds = [b"vault", match_account.key().as_ref()],
+        bump = match_account.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Mint da partida (só em partidas SPL)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault de tokens da partida (só em partidas SPL)
+    #[account(
+        mut,
+        seeds = [b"token_vault", match_account.key().as_ref()],
+        bump
+    )]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Conta de token do jogador, que recebe o stake de volta (só em partidas SPL)
+    #[account(mut)]
+    pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Programa de tokens, SPL Token ou Token-2022 (só em partidas SPL)
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Programa do sistema (pra transferir SOL do vault)
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts da instrução refund_bet
 ///
-/// - `bettor`: apostador (qualquer wallet pode apostar).
-/// - `match_account`: partida onde ele está apostando.
-/// - `participant`: conta que registra a aposta desse bettor nessa partida.
-///   - se não existir, é criada (init_if_needed)
-///   - se existir, é atualizada (a aposta acumula)
+/// Não precisa de signer específico: o reembolso sempre vai pro próprio apostador.
+#[event_cpi]
 #[derive(Accounts)]
-pub struct PlaceBet<'info> {
-    /// Apostador (assinante da transação)
+pub struct RefundBet<'info> {
+    /// Wallet do apostador que recebe a aposta de volta
     #[account(mut)]
-    pub bettor: Signer<'info>,
+    pub bettor: SystemAccount<'info>,
+
+    /// Configuração global (pausa do protocolo)
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
 
-    /// Conta da partida (escrow), que vai receber o SOL apostado
+    /// Match cancelada/empatada
     #[account(
         mut,
-        seeds = [b"match", match_account.arbiter.as_ref()],
+        seeds = [
+            b"match",
+            match_account.arbiter.as_ref(),
+            &match_account.id.to_le_bytes()
+        ],
         bump = match_account.bump
     )]
     pub match_account: Account<'info, Match>,
 
-    /// Conta de participação do apostador nessa partida.
-    /// Aqui assumimos UMA aposta por bettor por partida.
+    /// Vault de lamports da partida: guarda o SOL do escrow (e só isso)
     #[account(
-        init,
-        payer = bettor,
-        space = Participant::LEN,
+        mut,
+        seeds = [b"vault", match_account.key().as_ref()],
+        bump = match_account.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Conta de participação do apostador
+    #[account(
+        mut,
+        seeds = [b"participant", match_account.key().as_ref(), bettor.key().as_ref()],
+        bump
+    )]
+    pub participant: Account<'info, Participant>,
+
+    /// Mint da partida (só em partidas SPL)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault de tokens da partida (só em partidas SPL)
+    #[account(
+        mut,
+        seeds = [b"token_vault", match_account.key().as_ref()],
+        bump
+    )]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Conta de token do apostador, que recebe a aposta de volta (só em partidas SPL)
+    #[account(mut)]
+    pub bettor_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Programa de tokens, SPL Token ou Token-2022 (só em partidas SPL)
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Programa do sistema (pra transferir SOL do vault)
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts da instrução claim_unmatched_pool
+///
+/// Não precisa de signer específico: o pool sempre vai pro destino da política.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimUnmatchedPool<'info> {
+    /// CHECK: jogador vencedor ou tesouraria, validado no handler conforme a política
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// Configuração global (fornece a tesouraria e a pausa do protocolo)
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Match resolvida sem apostas no vencedor
+    #[account(
+        mut,
+        seeds = [
+            b"match",
+            match_account.arbiter.as_ref(),
+            &match_account.id.to_le_bytes()
+        ],
+        bump = match_account.bump
+    )]
+    pub match_account: Account<'info, Match>,
+
+    /// Vault de lamports da partida: guarda o SOL do escrow (e só isso)
+    #[account(
+        mut,
+        seeds = [b"vault", match_account.key().as_ref()],
+        bump = match_account.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Mint da partida (só em partidas SPL)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault de tokens da partida (só em partidas SPL)
+    #[account(
+        mut,
+        seeds = [b"token_vault", match_account.key().as_ref()],
+        bump
+    )]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Conta de token do destino do pool (só em partidas SPL)
+    #[account(mut)]
+    pub recipient_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Programa de tokens, SPL Token ou Token-2022 (só em partidas SPL)
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Programa do sistema (pra transferir SOL do vault)
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts da instrução sweep_dust
+///
+/// Não precisa de signer específico: a sobra sempre vai pro destino da política.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SweepDust<'info> {
+    /// CHECK: jogador vencedor ou tesouraria, validado no handler conforme a política
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// Configuração global (fornece a tesouraria e a pausa do protocolo)
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Match resolvida com todos os prêmios sacados
+    #[account(
+        mut,
+        seeds = [
+            b"match",
+            match_account.arbiter.as_ref(),
+            &match_account.id.to_le_bytes()
+        ],
+        bump = match_account.bump
+    )]
+    pub match_account: Account<'info, Match>,
+
+    /// Vault de lamports da partida: guarda o SOL do escrow (e só isso)
+    #[account(
+        mut,
+        seeds = [b"vault", match_account.key().as_ref()],
+        bump = match_account.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Mint da partida (só em partidas SPL)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault de tokens da partida (só em partidas SPL)
+    #[account(
+        mut,
+        seeds = [b"token_vault", match_account.key().as_ref()],
+        bump
+    )]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Conta de token do destino da sobra (só em partidas SPL)
+    #[account(mut)]
+    pub recipient_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Programa de tokens, SPL Token ou Token-2022 (só em partidas SPL)
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Programa do sistema (pra transferir SOL do vault)
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts da instrução sweep_unclaimed
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SweepUnclaimed<'info> {
+    /// Árbitro da partida ou admin do protocolo
+    pub authority: Signer<'info>,
+
+    /// Configuração global (fornece o admin, a tesouraria e a pausa do protocolo)
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: só recebe lamports; precisa ser a tesouraria da Config
+    #[account(mut, address = config.treasury @ CustomError::WrongRecipient)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// Match resolvida com o prazo de saque vencido
+    #[account(
+        mut,
+        seeds = [
+            b"match",
+            match_account.arbiter.as_ref(),
+            &match_account.id.to_le_bytes()
+        ],
+        bump = match_account.bump
+    )]
+    pub match_account: Account<'info, Match>,
+
+    /// Vault de lamports da partida: guarda o SOL do escrow (e só isso)
+    #[account(
+        mut,
+        seeds = [b"vault", match_account.key().as_ref()],
+        bump = match_account.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Mint da partida (só em partidas SPL)
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault de tokens da partida (só em partidas SPL)
+    #[account(
+        mut,
+        seeds = [b"token_vault", match_account.key().as_ref()],
+        bump
+    )]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Conta de token da tesouraria, que recebe o que não foi sacado (só em partidas SPL)
+    #[account(mut)]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Programa de tokens, SPL Token ou Token-2022 (só em partidas SPL)
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Programa do sistema (pra transferir SOL do vault)
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts da instrução close_participant
+///
+/// Não precisa de signer específico: o rent sempre volta pro próprio apostador.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseParticipant<'info> {
+    /// Wallet do apostador que pagou o rent da participação
+    #[account(mut)]
+    pub bettor: SystemAccount<'info>,
+
+    /// Match da participação
+    #[account(
+        mut,
+        seeds = [
+            b"match",
+            match_account.arbiter.as_ref(),
+            &match_account.id.to_le_bytes()
+        ],
+        bump = match_account.bump
+    )]
+    pub match_account: Account<'info, Match>,
+
+    /// Conta de participação a ser fechada
+    #[account(
+        mut,
+        close = bettor,
         seeds = [b"participant", match_account.key().as_ref(), bettor.key().as_ref()],
         bump
     )]
     pub participant: Account<'info, Participant>,
+}
+
+/// Accounts da instrução close_match
+///
+/// Não precisa de signer específico: o rent sempre volta pro árbitro que criou a Match.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseMatch<'info> {
+    /// Árbitro que pagou o rent da Match
+    #[account(mut, address = match_account.arbiter)]
+    pub arbiter: SystemAccount<'info>,
+
+    /// Match a ser fechada
+    #[account(
+        mut,
+        close = arbiter,
+        seeds = [
+            b"match",
+            match_account.arbiter.as_ref(),
+            &match_account.id.to_le_bytes()
+        ],
+        bump = match_account.bump
+    )]
+    pub match_account: Account<'info, Match>,
+
+    /// Vault de lamports da partida: guarda o SOL do escrow (e só isso)
+    #[account(
+        mut,
+        seeds = [b"vault", match_account.key().as_ref()],
+        bump = match_account.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Mint da partida (só em partidas SPL). Mutável pra receber as taxas de
+    /// transferência retidas no vault de tokens (Token-2022)
+    #[account(mut)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault de tokens da partida (só em partidas SPL)
+    #[account(
+        mut,
+        seeds = [b"token_vault", match_account.key().as_ref()],
+        bump
+    )]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Conta de token do árbitro, que recebe a sobra do vault (só em partidas SPL)
+    #[account(mut)]
+    pub arbiter_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Programa de tokens, SPL Token ou Token-2022 (só em partidas SPL)
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Programa do sistema (pra transferir SOL do vault)
+    pub system_program: Program<'info, System>,
+}
+
+/// Parâmetros das instruções initialize e update_config
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct ConfigArgs {
+    /// Conta que recebe os valores destinados ao protocolo
+    pub treasury: Pubkey,
+
+    /// Stake mínimo por jogador (em lamports)
+    pub min_stake_lamports: u64,
+
+    /// Aposta mínima (em lamports)
+    pub min_bet_lamports: u64,
+
+    /// Duração máxima de uma partida, da criação até `resolve_by` (em segundos)
+    pub max_match_duration: i64,
+
+    /// Taxa do protocolo (em basis points), enviada pra tesouraria
+    pub protocol_fee_bps: u16,
+
+    /// Teto da soma taxa do protocolo + taxa do árbitro (em basis points)
+    pub max_fee_bps: u16,
+
+    /// Prazo pros vencedores sacarem, contado da resolução (em segundos)
+    pub claim_window: i64,
+}
+
+/// Parâmetros da instrução create_match
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct CreateMatchArgs {
+    /// Precisa ser igual a `match_counter.next_id` (o id é atribuído on-chain;
+    /// o cliente só repete o valor pra conseguir derivar a PDA da Match)
+    pub id: u64,
+
+    /// Quanto cada jogador precisa depositar
+    pub stake_lamports: u64,
+
+    /// Prazo (Unix) para os dois jogadores depositarem; depois disso quem depositou
+    /// pode pedir W.O. (`claim_walkover`) ou a partida pode ser expirada
+    pub fund_by: i64,
+
+    /// Até quando (Unix) as apostas são aceitas
+    pub bets_close_at: i64,
+
+    /// Timestamp mínimo (Unix) para poder declarar o vencedor; não pode ser antes
+    /// de `bets_close_at` (pode ser igual, pra jogos curtos)
+    pub earliest_resolution: i64,
+
+    /// Timestamp máximo para declarar o vencedor; depois disso qualquer
+    /// um pode chamar `expire_match` e todo mundo recebe o dinheiro de volta
+    pub resolve_by: i64,
+
+    /// Wallets dos dois jogadores principais
+    pub player_a: Pubkey,
+    pub player_b: Pubkey,
+
+    /// O que fazer com o pool de apostas se ninguém apostou no vencedor
+    pub unmatched_pool_policy: UnmatchedPoolPolicy,
+
+    /// Taxa do árbitro (em basis points); somada à do protocolo não pode
+    /// passar de `Config.max_fee_bps`
+    pub arbiter_fee_bps: u16,
+}
+
+/// Status da partida (Match) no protocolo
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum MatchStatus {
+    Created,    // criada, ainda esperando depósitos/jogadores
+    Funded,     // os dois players já depositaram, apostas abertas
+    InProgress, // partida em andamento (start_match), apostas fechadas
+    Resolved,   // vencedor definido
+    Cancelled,  // match cancelada (pra reembolso)
+    Draw,       // empate (todo mundo recebe de volta)
+    Walkover,   // W.O.: só um jogador depositou, apostas devolvidas
+}
+
+/// Destino do pool de apostas quando ninguém apostou no lado vencedor
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum UnmatchedPoolPolicy {
+    RefundBettors, // cada apostador recebe a própria aposta de volta (refund_bet)
+    WinningPlayer, // o pool inteiro vai pro jogador vencedor (claim_unmatched_pool)
+    Treasury,      // o pool inteiro vai pra tesouraria do protocolo (claim_unmatched_pool)
+}
+
+/// Lado da aposta: Player A ou Player B
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Side {
+    PlayerA,
+    PlayerB,
+}
+
+/// Evento da initialize e da update_config, com os parâmetros aplicados
+#[event]
+pub struct ConfigUpdated {
+    pub admin: Pubkey,
+    pub args: ConfigArgs,
+}
+
+/// Evento da set_admin
+#[event]
+pub struct AdminChanged {
+    pub previous_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
+
+/// Evento da init_match_counter
+#[event]
+pub struct MatchCounterInitialized {
+    pub arbiter: Pubkey,
+    pub match_counter: Pubkey,
+}
+
+/// Evento da create_match
+#[event]
+pub struct MatchCreated {
+    pub match_pubkey: Pubkey,
+    pub match_id: u64,
+    pub arbiter: Pubkey,
+    pub player_a: Pubkey,
+    pub player_b: Pubkey,
+    pub stake_lamports: u64,
+    pub mint: Option<Pubkey>,
+    pub bets_close_at: i64,
+    pub earliest_resolution: i64,
+    pub resolve_by: i64,
+}
+
+/// Evento da join_as_player (`amount` é o que entrou de fato no escrow)
+#[event]
+pub struct PlayerJoined {
+    pub match_pubkey: Pubkey,
+    pub match_id: u64,
+    pub player: Pubkey,
+    pub amount: u64,
+    pub stake_pot: u64,
+    pub status: MatchStatus,
+}
+
+/// Evento da place_bet, com o total do apostador e os novos totais da partida
+#[event]
+pub struct BetPlaced {
+    pub match_pubkey: Pubkey,
+    pub match_id: u64,
+    pub bettor: Pubkey,
+    pub side: Side,
+    pub amount: u64,
+    pub bettor_total: u64,
+    pub total_side_a: u64,
+    pub total_side_b: u64,
+}
+
+/// Evento da declare_winner, com as taxas cobradas e o que sobrou pra distribuir
+#[event]
+pub struct WinnerDeclared {
+    pub match_pubkey: Pubkey,
+    pub match_id: u64,
+    pub arbiter: Pubkey,
+    pub winner: Side,
+    pub protocol_fee: u64,
+    pub arbiter_fee: u64,
+    pub stake_pot: u64,
+    pub payout_pool: u64,
+    pub claim_deadline: i64,
+}
+
+/// Evento da withdraw_winner_stake
+#[event]
+pub struct WinnerStakeWithdrawn {
+    pub match_pubkey: Pubkey,
+    pub match_id: u64,
+    pub winner: Pubkey,
+    pub amount: u64,
+}
+
+/// Evento da claim_bet_payout
+#[event]
+pub struct BetPayoutClaimed {
+    pub match_pubkey: Pubkey,
+    pub match_id: u64,
+    pub bettor: Pubkey,
+    pub side: Side,
+    pub amount: u64,
+}
+
+/// Evento da start_match (apostas fechadas)
+#[event]
+pub struct MatchStarted {
+    pub match_pubkey: Pubkey,
+    pub match_id: u64,
+    pub arbiter: Pubkey,
+    pub started_at: i64,
+}
 
-    /// Programa do sistema (para criar conta e transferir SOL)
-    pub system_program: Program<'info, System>,
+/// Evento da declare_draw
+#[event]
+pub struct DrawDeclared {
+    pub match_pubkey: Pubkey,
+    pub match_id: u64,
+    pub arbiter: Pubkey,
 }
 
-/// Accounts da instrução declare_winner
-#[derive(Accounts)]
-pub struct DeclareWinner<'info> {
-    /// Árbitro que está declarando o resultado
-    #[account(mut)]
-    pub arbiter: Signer<'info>,
+/// Evento da declare_walkover e da claim_walkover (`declared_by` é o árbitro
+/// ou o jogador que reivindicou)
+#[event]
+pub struct WalkoverDeclared {
+    pub match_pubkey: Pubkey,
+    pub match_id: u64,
+    pub declared_by: Pubkey,
+    pub winner: Side,
+}
 
-    /// Match a ser atualizada
-    #[account(
-        mut,
-        seeds = [b"match", match_account.arbiter.as_ref()],
-        bump = match_account.bump
-    )]
-    pub match_account: Account<'info, Match>,
+/// Evento da cancel_match
+#[event]
+pub struct MatchCancelled {
+    pub match_pubkey: Pubkey,
+    pub match_id: u64,
+    pub arbiter: Pubkey,
 }
 
-/// Accounts da instrução withdraw_winner_stake
-#[derive(Accounts)]
-pub struct WithdrawWinnerStake<'info> {
-    /// Jogador vencedor (PlayerA ou PlayerB)
-    #[account(mut)]
-    pub winner: Signer<'info>,
+/// Evento da expire_match (`funding_expired`: expirou por falta de depósito)
+#[event]
+pub struct MatchExpired {
+    pub match_pubkey: Pubkey,
+    pub match_id: u64,
+    pub funding_expired: bool,
+}
 
-    /// Match que guarda o escrow
-    #[account(
-        mut,
-        seeds = [b"match", match_account.arbiter.as_ref()],
-        bump = match_account.bump
-    )]
-    pub match_account: Account<'info, Match>,
+/// Evento da refund_player, com o que ainda sobrou no pote de stakes
+#[event]
+pub struct PlayerRefunded {
+    pub match_pubkey: Pubkey,
+    pub match_id: u64,
+    pub player: Pubkey,
+    pub amount: u64,
+    pub stake_pot: u64,
 }
 
-/// Accounts da instrução claim_bet_payout
-#[derive(Accounts)]
-pub struct ClaimBetPayout<'info> {
-    /// Apostador que está sacando
-    #[account(mut)]
-    pub bettor: Signer<'info>,
+/// Evento da refund_bet
+#[event]
+pub struct BetRefunded {
+    pub match_pubkey: Pubkey,
+    pub match_id: u64,
+    pub bettor: Pubkey,
+    pub side: Side,
+    pub amount: u64,
+}
 
-    /// Match resolvida
-    #[account(
-        mut,
-        seeds = [b"match", match_account.arbiter.as_ref()],
-        bump = match_account.bump
-    )]
-    pub match_account: Account<'info, Match>,
+/// Evento da claim_unmatched_pool
+#[event]
+pub struct UnmatchedPoolClaimed {
+    pub match_pubkey: Pubkey,
+    pub match_id: u64,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
 
-    /// Conta de participação do apostador
-    #[account(
-        mut,
-        seeds = [b"participant", match_account.key().as_ref(), bettor.key().as_ref()],
-        bump
-    )]
-    pub participant: Account<'info, Participant>,
+/// Evento da sweep_dust (`amount` é a sobra de arredondamento, pode ser 0)
+#[event]
+pub struct DustSwept {
+    pub match_pubkey: Pubkey,
+    pub match_id: u64,
+    pub recipient: Pubkey,
+    pub amount: u64,
 }
 
+/// Evento da sweep_unclaimed (`amount` é tudo que não foi sacado até o prazo)
+#[event]
+pub struct UnclaimedSwept {
+    pub match_pubkey: Pubkey,
+    pub match_id: u64,
+    pub authority: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,
+}
 
+/// Evento da set_paused
+#[event]
+pub struct PauseUpdated {
+    pub admin: Pubkey,
+    pub paused: bool,
+    pub withdrawals_paused: bool,
+}
 
-/// Status da partida (Match) no protocolo
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
-pub enum MatchStatus {
-    Created,    // criada, ainda esperando depósitos/jogadores
-    Funded,     // os dois players já depositaram
-    InProgress, // partida em andamento
-    Resolved,   // vencedor definido
-    Cancelled,  // match cancelada (pra reembolso)
+/// Evento da set_match_suspended
+#[event]
+pub struct MatchSuspensionUpdated {
+    pub match_pubkey: Pubkey,
+    pub match_id: u64,
+    pub authority: Pubkey,
+    pub suspended: bool,
+    pub withdrawals_suspended: bool,
 }
 
-/// Lado da aposta: Player A ou Player B
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
-pub enum Side {
-    PlayerA,
-    PlayerB,
+/// Evento da close_participant, com quantas participações ainda estão abertas
+#[event]
+pub struct ParticipantClosed {
+    pub match_pubkey: Pubkey,
+    pub match_id: u64,
+    pub bettor: Pubkey,
+    pub open_participants: u32,
+}
+
+/// Evento da close_match
+#[event]
+pub struct MatchClosed {
+    pub match_pubkey: Pubkey,
+    pub match_id: u64,
+    pub arbiter: Pubkey,
 }
 
 /// Conta principal da partida de aposta da cobrinha
@@ -550,8 +3006,8 @@ pub struct Match {
     pub total_side_a: u64,
     pub total_side_b: u64,
 
-    /// Timestamp mínimo para poder declarar vencedor
-    pub deadline: i64,
+    /// Até quando as apostas são aceitas
+    pub bets_close_at: i64,
 
     /// Status da partida
     pub status: MatchStatus,
@@ -562,7 +3018,7 @@ pub struct Match {
     /// Bump do PDA da match
     pub bump: u8,
 
-    /// ID da partida (pode ser incremental ou gerado off-chain)
+    /// ID da partida (atribuído pelo `MatchCounter` do árbitro)
     pub id: u64,
 
     /// Flags indicando se cada player já depositou o stake
@@ -571,6 +3027,93 @@ pub struct Match {
 
     // Já sacou os stakes (2x stake_lamports)?
     pub stakes_withdrawn: bool,
+
+    /// Timestamp máximo para declarar vencedor; depois disso a partida pode ser expirada
+    pub resolve_by: i64,
+
+    /// Stake realmente depositado pelos jogadores e ainda no escrow
+    /// (separado do pool de apostas)
+    pub stake_pot: u64,
+
+    /// Prazo para os dois jogadores depositarem
+    pub fund_by: i64,
+
+    /// Contas de participação ainda não fechadas
+    pub open_participants: u32,
+
+    /// O que fazer com o pool de apostas se ninguém apostou no vencedor
+    pub unmatched_pool_policy: UnmatchedPoolPolicy,
+
+    /// Pool sem apostas no vencedor já foi pago ao jogador vencedor?
+    pub unmatched_pool_claimed: bool,
+
+    /// Taxas da partida (em basis points), congeladas na criação
+    pub protocol_fee_bps: u16,
+    pub arbiter_fee_bps: u16,
+
+    /// Taxas efetivamente cobradas na resolução (em lamports)
+    pub protocol_fee: u64,
+    pub arbiter_fee: u64,
+
+    /// Pool de apostas líquido (já sem taxas) dividido entre os vencedores
+    pub payout_pool: u64,
+
+    /// Mint da partida (None = SOL nativo). Em partidas SPL, `stake_lamports`,
+    /// apostas e taxas são em unidades do token.
+    pub mint: Option<Pubkey>,
+
+    /// Quanto do stake de cada jogador entrou de fato no escrow
+    /// (menos que `stake_lamports` se o mint cobrar taxa de transferência)
+    pub player_a_stake: u64,
+    pub player_b_stake: u64,
+
+    /// Bump do PDA do vault de lamports da partida
+    pub vault_bump: u8,
+
+    /// Quanto a partida ainda deve (stakes, apostas e taxas ainda não pagos).
+    /// O vault (de lamports ou de tokens) sempre cobre pelo menos esse valor.
+    pub escrowed: u64,
+
+    /// Quando o árbitro iniciou o jogo (`start_match`); 0 se ainda não começou
+    pub started_at: i64,
+
+    /// Timestamp mínimo para poder declarar o resultado
+    pub earliest_resolution: i64,
+
+    /// Quantos apostadores (contas de participação) apostaram em cada lado
+    pub participants_side_a: u32,
+    pub participants_side_b: u32,
+
+    /// Quantos apostadores vencedores já sacaram o prêmio
+    pub payouts_claimed: u32,
+
+    /// Sobra de arredondamento do pool já foi varrida (`sweep_dust`)?
+    pub dust_swept: bool,
+
+    /// Prazo pros vencedores sacarem, contado da resolução (em segundos),
+    /// congelado na criação
+    pub claim_window: i64,
+
+    /// Até quando (Unix) os vencedores podem sacar; 0 enquanto não tem vencedor.
+    /// Depois disso o que sobrou vai pra tesouraria (`sweep_unclaimed`).
+    pub claim_deadline: i64,
+
+    /// O que não foi sacado até `claim_deadline` já foi pra tesouraria?
+    pub unclaimed_swept: bool,
+
+    /// Partida suspensa pelo árbitro/admin: sem depósitos nem apostas
+    pub suspended: bool,
+
+    /// Saques, reembolsos e varreduras da partida suspensos pelo árbitro/admin
+    pub withdrawals_suspended: bool,
+
+    /// Desde quando (Unix) os saques da partida estão suspensos (ou desde a
+    /// resolução, se já estavam suspensos nela)
+    pub withdrawals_suspended_at: i64,
+
+    /// `Config.withdrawals_paused_secs_at` na resolução: só a pausa do protocolo
+    /// depois disso adia o prazo de saque
+    pub paused_secs_at_resolution: i64,
 }
 
 impl Match {
@@ -579,14 +3122,376 @@ impl Match {
     pub const LEN: usize =
         8 +         // discriminator
         32 * 3 +   // arbiter, player_a, player_b
-        8 * 4 +    // stake_lamports, total_side_a, total_side_b, deadline
+        8 * 4 +    // stake_lamports, total_side_a, total_side_b, bets_close_at
         1 +        // status
         2 +        // winner (Option<Side>) ~ 2 bytes é suficiente
         1 +        // bump
         8 +        // id
         1 +        // player_a_deposited
         1 +        // player_b_deposited
-        1;         // stakes_withdrawn
+        1 +        // stakes_withdrawn
+        8 +        // resolve_by
+        8 +        // stake_pot
+        8 +        // fund_by
+        4 +        // open_participants
+        1 +        // unmatched_pool_policy
+        1 +        // unmatched_pool_claimed
+        2 * 2 +    // protocol_fee_bps, arbiter_fee_bps
+        8 * 3 +    // protocol_fee, arbiter_fee, payout_pool
+        1 + 32 +   // mint (Option<Pubkey>)
+        8 * 2 +    // player_a_stake, player_b_stake
+        1 +        // vault_bump
+        8 +        // escrowed
+        8 +        // started_at
+        8 +        // earliest_resolution
+        4 * 3 +    // participants_side_a, participants_side_b, payouts_claimed
+        1 +        // dust_swept
+        8 * 2 +    // claim_window, claim_deadline
+        1 +        // unclaimed_swept
+        1 +        // suspended
+        1 +        // withdrawals_suspended
+        8 * 2;     // withdrawals_suspended_at, paused_secs_at_resolution
+
+    /// Verifica se a partida pode receber um resultado (vencedor ou empate) agora.
+    pub fn check_resolvable(&self, now: i64) -> Result<()> {
+        // Já resolvida?
+        require!(self.winner.is_none(), CustomError::AlreadyResolved);
+
+        // Os dois jogadores precisam ter depositado (senão é caso de W.O.)
+        require!(self.status != MatchStatus::Created, CustomError::MatchNotFunded);
+
+        // O jogo precisa ter começado (`start_match`)
+        require!(self.status != MatchStatus::Funded, CustomError::MatchNotStarted);
+        require!(self.status == MatchStatus::InProgress, CustomError::InvalidStatus);
+
+        // Resultado só a partir do primeiro momento de resolução
+        require!(now >= self.earliest_resolution, CustomError::TooEarly);
+
+        // Depois do prazo de resolução a partida só pode ser expirada
+        require!(now < self.resolve_by, CustomError::ResolutionExpired);
+
+        Ok(())
+    }
+
+    /// Encerra a partida por W.O.: o único jogador que depositou vence.
+    pub fn apply_walkover(&mut self, now: i64) -> Result<()> {
+        require!(self.status == MatchStatus::Created, CustomError::InvalidStatus);
+
+        // Só depois do prazo de depósito (antes disso o outro jogador ainda pode entrar)
+        require!(now >= self.fund_by, CustomError::FundingNotClosed);
+
+        // Depois do prazo de resolução a partida só pode ser expirada
+        require!(now < self.resolve_by, CustomError::ResolutionExpired);
+
+        // Exatamente um jogador precisa ter depositado
+        let winner = match (self.player_a_deposited, self.player_b_deposited) {
+            (true, false) => Side::PlayerA,
+            (false, true) => Side::PlayerB,
+            _ => return err!(CustomError::WalkoverNotAllowed),
+        };
+
+        self.winner = Some(winner);
+        self.status = MatchStatus::Walkover;
+
+        Ok(())
+    }
+
+    /// Partida em que os jogadores recebem o stake de volta
+    pub fn stakes_refundable(&self) -> bool {
+        self.status == MatchStatus::Cancelled || self.status == MatchStatus::Draw
+    }
+
+    /// Partida em que os apostadores recebem a aposta de volta
+    pub fn bets_refundable(&self) -> bool {
+        self.stakes_refundable() || self.status == MatchStatus::Walkover
+    }
+
+    /// Total apostado no lado vencedor (0 se ainda não tem vencedor)
+    pub fn winner_bets_total(&self) -> u64 {
+        match self.winner {
+            Some(Side::PlayerA) => self.total_side_a,
+            Some(Side::PlayerB) => self.total_side_b,
+            None => 0,
+        }
+    }
+
+    /// Depósitos e apostas liberados: nem o protocolo nem a partida pausados
+    pub fn check_deposits_open(&self, config: &Config) -> Result<()> {
+        require!(!config.paused, CustomError::ProtocolPaused);
+        require!(!self.suspended, CustomError::MatchSuspended);
+
+        Ok(())
+    }
+
+    /// Saques, reembolsos e varreduras liberados: nem o protocolo nem a
+    /// partida com os saques pausados
+    pub fn check_withdrawals_open(&self, config: &Config) -> Result<()> {
+        require!(!config.withdrawals_paused, CustomError::WithdrawalsPaused);
+        require!(
+            !self.withdrawals_suspended,
+            CustomError::MatchWithdrawalsSuspended
+        );
+
+        Ok(())
+    }
+
+    /// Prazo de saque efetivo: `claim_deadline` adiado pelo tempo em que os
+    /// saques do protocolo ficaram pausados desde a resolução
+    pub fn claim_deadline_at(&self, config: &Config, now: i64) -> i64 {
+        let paused = config
+            .withdrawals_paused_secs_at(now)
+            .saturating_sub(self.paused_secs_at_resolution);
+        self.claim_deadline.saturating_add(paused)
+    }
+
+    /// Ainda dá pra sacar? Só partidas resolvidas (`declare_winner`) têm prazo;
+    /// reembolsos de partidas canceladas, empatadas ou por W.O. não vencem.
+    pub fn claim_window_open(&self, config: &Config, now: i64) -> bool {
+        self.status != MatchStatus::Resolved || now < self.claim_deadline_at(config, now)
+    }
+
+    /// Saques da partida retomados: numa partida resolvida, adia `claim_deadline`
+    /// pelo tempo em que ficaram suspensos
+    pub fn extend_claim_deadline(&mut self, now: i64) -> Result<()> {
+        if self.status == MatchStatus::Resolved {
+            let suspended = now.saturating_sub(self.withdrawals_suspended_at);
+            self.claim_deadline = self
+                .claim_deadline
+                .checked_add(suspended)
+                .ok_or(CustomError::MathOverflow)?;
+        }
+
+        Ok(())
+    }
+
+    /// Quantos apostadores apostaram no lado vencedor (0 se ainda não tem vencedor)
+    pub fn winner_participants(&self) -> u32 {
+        match self.winner {
+            Some(Side::PlayerA) => self.participants_side_a,
+            Some(Side::PlayerB) => self.participants_side_b,
+            None => 0,
+        }
+    }
+
+    /// Sobra do pool de apostas que não é de nenhum apostador: o que o escrow
+    /// ainda deve fora o pote de stakes. Só é sobra de fato depois que todos os
+    /// vencedores sacaram.
+    pub fn pool_dust(&self) -> Result<u64> {
+        Ok(self
+            .escrowed
+            .checked_sub(self.stake_pot)
+            .ok_or(CustomError::EscrowInvariantViolated)?)
+    }
+
+    /// Destino da sobra de arredondamento: jogador vencedor com política
+    /// `WinningPlayer`, tesouraria nas outras
+    pub fn dust_recipient(&self, treasury: Pubkey) -> Result<Pubkey> {
+        Ok(match self.unmatched_pool_policy {
+            UnmatchedPoolPolicy::WinningPlayer => match self.winner {
+                Some(Side::PlayerA) => self.player_a,
+                Some(Side::PlayerB) => self.player_b,
+                None => return err!(CustomError::NoWinner),
+            },
+            UnmatchedPoolPolicy::Treasury | UnmatchedPoolPolicy::RefundBettors => treasury,
+        })
+    }
+
+    /// Desconta as taxas do protocolo e do árbitro na resolução.
+    ///
+    /// As taxas incidem sobre o pote de stakes e sobre o pool de apostas, exceto
+    /// quando ninguém apostou no vencedor e as apostas vão ser reembolsadas.
+    pub fn apply_fees(&mut self) -> Result<()> {
+        let pool_bets = self
+            .total_side_a
+            .checked_add(self.total_side_b)
+            .ok_or(CustomError::MathOverflow)?;
+
+        let refund_unmatched = self.pool_unmatched()
+            && self.unmatched_pool_policy == UnmatchedPoolPolicy::RefundBettors;
+
+        let split = settlement::split_fees(
+            self.stake_pot,
+            pool_bets,
+            !refund_unmatched,
+            self.protocol_fee_bps,
+            self.arbiter_fee_bps,
+        )?;
+
+        self.stake_pot = split.stake_pot;
+        self.payout_pool = split.payout_pool;
+        self.protocol_fee = split.protocol_fee;
+        self.arbiter_fee = split.arbiter_fee;
+
+        Ok(())
+    }
+
+    /// Partida resolvida com apostas, mas nenhuma no lado vencedor
+    pub fn pool_unmatched(&self) -> bool {
+        self.status == MatchStatus::Resolved
+            && self.winner_bets_total() == 0
+            && self.total_side_a.saturating_add(self.total_side_b) > 0
+    }
+
+    /// Destino do pool sem apostas no vencedor, conforme a política da partida
+    /// (com `RefundBettors` não tem destino: cada apostador recebe a sua aposta)
+    pub fn unmatched_pool_recipient(&self, treasury: Pubkey) -> Result<Pubkey> {
+        match self.unmatched_pool_policy {
+            UnmatchedPoolPolicy::WinningPlayer => match self.winner {
+                Some(Side::PlayerA) => Ok(self.player_a),
+                Some(Side::PlayerB) => Ok(self.player_b),
+                None => err!(CustomError::NoWinner),
+            },
+            UnmatchedPoolPolicy::Treasury => Ok(treasury),
+            UnmatchedPoolPolicy::RefundBettors => err!(CustomError::WrongUnmatchedPoolPolicy),
+        }
+    }
+
+    /// Partida encerrada, com stakes sacados/reembolsados, nada mais devido pelo
+    /// escrow e todas as participações fechadas: já pode fechar a conta.
+    pub fn is_settled(&self) -> bool {
+        let finished = self.status == MatchStatus::Resolved || self.bets_refundable();
+
+        // Com política `WinningPlayer` ou `Treasury`, o pool precisa ter sido sacado
+        // pelo destino da política (com `RefundBettors` os apostadores só fecham
+        // depois do reembolso; depois do `sweep_unclaimed` não tem mais nada pendente)
+        let pool_pending = self.pool_unmatched()
+            && self.unmatched_pool_policy != UnmatchedPoolPolicy::RefundBettors
+            && !self.unmatched_pool_claimed
+            && !self.unclaimed_swept;
+
+        // Com apostas no vencedor, a sobra de arredondamento precisa ter sido varrida
+        let dust_pending = self.status == MatchStatus::Resolved
+            && self.winner_bets_total() > 0
+            && !self.dust_swept
+            && !self.unclaimed_swept;
+
+        finished
+            && !pool_pending
+            && !dust_pending
+            && self.stake_pot == 0
+            && self.escrowed == 0
+            && self.open_participants == 0
+    }
+}
+
+/// Configuração global do protocolo (PDA única, seeds = ["config"])
+#[account]
+pub struct Config {
+    /// Autoridade do protocolo: única conta que pode alterar a configuração
+    pub admin: Pubkey,
+
+    /// Conta que recebe os valores destinados ao protocolo
+    pub treasury: Pubkey,
+
+    /// Stake mínimo por jogador (em lamports)
+    pub min_stake_lamports: u64,
+
+    /// Aposta mínima (em lamports)
+    pub min_bet_lamports: u64,
+
+    /// Duração máxima de uma partida, da criação até `resolve_by` (em segundos)
+    pub max_match_duration: i64,
+
+    /// Bump do PDA da configuração
+    pub bump: u8,
+
+    /// Taxa do protocolo (em basis points), enviada pra tesouraria
+    pub protocol_fee_bps: u16,
+
+    /// Teto da soma taxa do protocolo + taxa do árbitro (em basis points)
+    pub max_fee_bps: u16,
+
+    /// Prazo pros vencedores sacarem, contado da resolução (em segundos)
+    pub claim_window: i64,
+
+    /// Protocolo pausado pelo admin: sem partidas novas, depósitos nem apostas
+    pub paused: bool,
+
+    /// Saques, reembolsos e varreduras pausados pelo admin
+    pub withdrawals_paused: bool,
+
+    /// Desde quando (Unix) os saques estão pausados
+    pub withdrawals_paused_at: i64,
+
+    /// Total de segundos com saques pausados, somando só as pausas já encerradas
+    pub withdrawals_paused_secs: i64,
+}
+
+impl Config {
+    /// Tamanho em bytes da conta Config (inclui discriminador)
+    pub const LEN: usize =
+        8 +   // discriminator
+        32 +  // admin
+        32 +  // treasury
+        8 +   // min_stake_lamports
+        8 +   // min_bet_lamports
+        8 +   // max_match_duration
+        1 +   // bump
+        2 +   // protocol_fee_bps
+        2 +   // max_fee_bps
+        8 +   // claim_window
+        1 +   // paused
+        1 +   // withdrawals_paused
+        8 * 2; // withdrawals_paused_at, withdrawals_paused_secs
+
+    /// Total de segundos com saques pausados até `now`, contando a pausa atual
+    pub fn withdrawals_paused_secs_at(&self, now: i64) -> i64 {
+        if self.withdrawals_paused {
+            self.withdrawals_paused_secs
+                .saturating_add(now.saturating_sub(self.withdrawals_paused_at))
+        } else {
+            self.withdrawals_paused_secs
+        }
+    }
+
+    /// Valida e aplica os parâmetros globais
+    pub fn apply(&mut self, args: ConfigArgs) -> Result<()> {
+        require!(args.max_match_duration > 0, CustomError::InvalidConfig);
+        require!(args.claim_window > 0, CustomError::InvalidConfig);
+
+        // teto configurado não pode passar do teto absoluto, e a taxa do
+        // protocolo sozinha precisa caber no teto
+        require!(args.max_fee_bps <= MAX_FEE_BPS, CustomError::FeeTooHigh);
+        require!(
+            args.protocol_fee_bps <= args.max_fee_bps,
+            CustomError::FeeTooHigh
+        );
+
+        self.treasury = args.treasury;
+        self.min_stake_lamports = args.min_stake_lamports;
+        self.min_bet_lamports = args.min_bet_lamports;
+        self.max_match_duration = args.max_match_duration;
+        self.protocol_fee_bps = args.protocol_fee_bps;
+        self.max_fee_bps = args.max_fee_bps;
+        self.claim_window = args.claim_window;
+
+        Ok(())
+    }
+}
+
+/// Contador de partidas de um árbitro.
+///
+/// Guarda o próximo id a ser usado, então o endereço da próxima Match
+/// pode ser derivado de forma determinística pelos clientes.
+#[account]
+pub struct MatchCounter {
+    /// Árbitro dono do contador
+    pub arbiter: Pubkey,
+
+    /// Próximo id de partida a ser atribuído
+    pub next_id: u64,
+
+    /// Bump do PDA do contador
+    pub bump: u8,
+}
+
+impl MatchCounter {
+    /// Tamanho em bytes da conta MatchCounter (inclui discriminador)
+    pub const LEN: usize =
+        8 +   // discriminator
+        32 +  // arbiter
+        8 +   // next_id
+        1;    // bump
 }
 
 /// Conta de participação/aposta de um usuário em uma Match
@@ -601,7 +3506,8 @@ pub struct Participant {
     /// Lado em que apostou (PlayerA ou PlayerB)
     pub side: Side,
 
-    /// Quantidade apostada em lamports
+    /// Quantidade apostada em lamports (ou unidades do token), já descontada
+    /// qualquer taxa de transferência do mint
     pub amount: u64,
 
     /// Já sacou o prêmio/recebeu reembolso?
@@ -617,11 +3523,24 @@ impl Participant {
         1 +   // side
         8 +   // amount
         1;    // claimed
+
+    /// Não tem mais nada pra receber nessa partida: já sacou/foi reembolsado,
+    /// apostou no lado perdedor de uma partida resolvida (a não ser que
+    /// ninguém tenha apostado no vencedor e a política seja de reembolso), ou
+    /// perdeu o prazo de saque e o saldo já foi pra tesouraria.
+    pub fn is_settled(&self, m: &Match) -> bool {
+        let refund_pending = m.pool_unmatched()
+            && m.unmatched_pool_policy == UnmatchedPoolPolicy::RefundBettors;
+        let lost = m.status == MatchStatus::Resolved
+            && m.winner != Some(self.side)
+            && !refund_pending;
+        self.claimed || lost || m.unclaimed_swept
+    }
 }
 
 #[error_code]
 pub enum CustomError {
-    #[msg("Deadline must be in the future")]
+    #[msg("Bets close time must be in the future")]
     InvalidDeadline,
 
     #[msg("Stake must be greater than zero")]
@@ -677,5 +3596,107 @@ pub enum CustomError {
 
     #[msg("There are no bets on the winner side")]
     NoBetsOnWinnerSide,
+
+    #[msg("Match id does not match the arbiter's next match id")]
+    MatchIdMismatch,
+
+    #[msg("There is nothing to refund for this account")]
+    NothingToRefund,
+
+    #[msg("Resolution deadline must be after the earliest resolution time")]
+    InvalidResolveBy,
+
+    #[msg("Resolution deadline has passed, the match can only be expired")]
+    ResolutionExpired,
+
+    #[msg("Resolution deadline has not passed yet")]
+    ResolutionNotExpired,
+
+    #[msg("Both players must deposit before the match can be resolved")]
+    MatchNotFunded,
+
+    #[msg("Walkover requires exactly one player to have deposited")]
+    WalkoverNotAllowed,
+
+    #[msg("Funding deadline must be in the future and not after bets close")]
+    InvalidFundBy,
+
+    #[msg("Funding deadline has passed")]
+    FundingClosed,
+
+    #[msg("Funding deadline has not passed yet")]
+    FundingNotClosed,
+
+    #[msg("Participant still has funds to claim or refund")]
+    ParticipantNotSettled,
+
+    #[msg("Match still holds stakes, bets or open participants")]
+    MatchNotSettled,
+
+    #[msg("Match is not resolved with an unmatched bet pool")]
+    PoolNotUnmatched,
+
+    #[msg("Match unmatched pool policy does not allow this operation")]
+    WrongUnmatchedPoolPolicy,
+
+    #[msg("Only the protocol admin can perform this action")]
+    NotAdmin,
+
+    #[msg("Invalid protocol configuration")]
+    InvalidConfig,
+
+    #[msg("Stake is below the protocol minimum")]
+    StakeBelowMinimum,
+
+    #[msg("Bet is below the protocol minimum")]
+    BetBelowMinimum,
+
+    #[msg("Match duration exceeds the protocol maximum")]
+    MatchTooLong,
+
+    #[msg("Recipient account does not match the expected destination")]
+    WrongRecipient,
+
+    #[msg("Fees exceed the protocol maximum")]
+    FeeTooHigh,
+
+    #[msg("Token accounts are required for SPL token matches")]
+    MissingTokenAccounts,
+
+    #[msg("Token account has the wrong mint or owner")]
+    InvalidTokenAccount,
+
+    #[msg("Escrow balance does not cover the match obligations")]
+    EscrowInvariantViolated,
+
+    #[msg("Match has not been started by the arbiter")]
+    MatchNotStarted,
+
+    #[msg("Earliest resolution time must not be before bets close")]
+    InvalidEarliestResolution,
+
+    #[msg("Not all winning bettors have claimed their payouts")]
+    PayoutsPending,
+
+    #[msg("Claim deadline has passed")]
+    ClaimWindowExpired,
+
+    #[msg("Claim deadline has not passed yet")]
+    ClaimWindowOpen,
+
+    #[msg("Only the match arbiter or the protocol admin can perform this action")]
+    NotMatchAuthority,
+
+    #[msg("Protocol is paused: no new matches, deposits or bets")]
+    ProtocolPaused,
+
+    #[msg("Match is suspended: no deposits or bets")]
+    MatchSuspended,
+
+    #[msg("Protocol withdrawals are paused")]
+    WithdrawalsPaused,
+
+    #[msg("Match withdrawals are suspended")]
+    MatchWithdrawalsSuspended,
 }
 
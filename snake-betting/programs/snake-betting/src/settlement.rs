@@ -0,0 +1,94 @@
+//! Matemática da liquidação das partidas, sem acesso a contas.
+//!
+//! As funções daqui são puras: os handlers do programa usam elas pra calcular
+//! taxas e prêmios, e os clientes off-chain podem usar as mesmas funções pra
+//! prever quanto cada um vai receber.
+//!
+//! Todo arredondamento é pra baixo, então a soma dos prêmios nunca passa do
+//! pool; a sobra (dust) fica no escrow.
+
+use anchor_lang::prelude::*;
+
+use crate::{CustomError, BPS_DENOMINATOR};
+
+/// Valores da partida depois de descontadas as taxas na resolução
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeeSplit {
+    /// Pote de stakes que sobra pro jogador vencedor
+    pub stake_pot: u64,
+
+    /// Pool de apostas que sobra pros apostadores do lado vencedor
+    pub payout_pool: u64,
+
+    /// Total que vai pra tesouraria
+    pub protocol_fee: u64,
+
+    /// Total que vai pro árbitro
+    pub arbiter_fee: u64,
+}
+
+/// Parcela `bps` (em basis points) de `amount`, arredondada pra baixo.
+pub fn bps_of(amount: u64, bps: u16) -> Result<u64> {
+    let value = (amount as u128)
+        .checked_mul(bps as u128)
+        .ok_or(CustomError::MathOverflow)?
+        / (BPS_DENOMINATOR as u128);
+
+    Ok(u64::try_from(value).map_err(|_| CustomError::MathOverflow)?)
+}
+
+/// Desconta as taxas do protocolo e do árbitro do pote de stakes e do pool de
+/// apostas.
+///
+/// Com `charge_pool = false` (pool que vai ser reembolsado) as taxas incidem
+/// só sobre os stakes. A soma das taxas não pode passar de 100%.
+pub fn split_fees(
+    stake_pot: u64,
+    pool_bets: u64,
+    charge_pool: bool,
+    protocol_fee_bps: u16,
+    arbiter_fee_bps: u16,
+) -> Result<FeeSplit> {
+    let fee_pool = if charge_pool { pool_bets } else { 0 };
+
+    let protocol_stake_fee = bps_of(stake_pot, protocol_fee_bps)?;
+    let arbiter_stake_fee = bps_of(stake_pot, arbiter_fee_bps)?;
+    let protocol_pool_fee = bps_of(fee_pool, protocol_fee_bps)?;
+    let arbiter_pool_fee = bps_of(fee_pool, arbiter_fee_bps)?;
+
+    Ok(FeeSplit {
+        stake_pot: stake_pot
+            .checked_sub(protocol_stake_fee)
+            .and_then(|v| v.checked_sub(arbiter_stake_fee))
+            .ok_or(CustomError::MathOverflow)?,
+        payout_pool: pool_bets
+            .checked_sub(protocol_pool_fee)
+            .and_then(|v| v.checked_sub(arbiter_pool_fee))
+            .ok_or(CustomError::MathOverflow)?,
+        protocol_fee: protocol_stake_fee
+            .checked_add(protocol_pool_fee)
+            .ok_or(CustomError::MathOverflow)?,
+        arbiter_fee: arbiter_stake_fee
+            .checked_add(arbiter_pool_fee)
+            .ok_or(CustomError::MathOverflow)?,
+    })
+}
+
+/// Prêmio de uma aposta vencedora: `amount * payout_pool / winner_bets_total`,
+/// arredondado pra baixo.
+///
+/// `amount` é a aposta do apostador e `winner_bets_total` o total apostado no
+/// lado vencedor (que inclui `amount`).
+pub fn bet_payout(amount: u64, payout_pool: u64, winner_bets_total: u64) -> Result<u64> {
+    // Não pode dividir por zero: precisa existir apostas no lado vencedor
+    require!(winner_bets_total > 0, CustomError::NoBetsOnWinnerSide);
+    require!(amount <= winner_bets_total, CustomError::MathOverflow);
+
+    // Em u128 o produto nunca estoura; como amount <= total, o resultado cabe em u64
+    let payout = (amount as u128)
+        .checked_mul(payout_pool as u128)
+        .ok_or(CustomError::MathOverflow)?
+        / (winner_bets_total as u128);
+
+    Ok(u64::try_from(payout).map_err(|_| CustomError::MathOverflow)?)
+}
@@ -0,0 +1,800 @@
+//! Harness dos testes de integração: roda o programa `snake_betting` compilado
+//! dentro do `solana-program-test` (SVM em processo), com os programas SPL
+//! Token, Token-2022 e ATA já carregados, e permite avançar o relógio à vontade.
+//!
+//! Os testes carregam o `.so` gerado pelo `anchor build` (ou do diretório em
+//! `SBF_OUT_DIR`), então ficam marcados com `#[ignore]` e rodam com
+//! `scripts/test-rust.sh` (é o que a CI roda), ou à mão com
+//! `anchor build && cargo test -p snake-betting -- --ignored`.
+//! O modo nativo do program-test não serve: no Anchor 0.32 as CPIs passam pelo
+//! `solana-invoke`, que só funciona com `target_os = "solana"`.
+
+#![allow(dead_code)]
+
+use std::collections::HashSet;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::system_instruction;
+use anchor_spl::associated_token::spl_associated_token_account;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    transfer_fee, BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+};
+use snake_betting_client::{
+    accounts, instructions, pda, Config, ConfigArgs, CreateMatchArgs, CustomError, Match,
+    Participant, Side, SplMint, UnmatchedPoolPolicy,
+};
+use solana_program_test::{ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    bpf_loader,
+    clock::Clock,
+    instruction::{Instruction, InstructionError},
+    native_token::LAMPORTS_PER_SOL,
+    signature::{Keypair, Signature, Signer},
+    transaction::{Transaction, TransactionError},
+};
+
+/// Stake padrão por jogador
+pub const STAKE: u64 = LAMPORTS_PER_SOL;
+
+/// Saldo inicial de cada wallet criada pelo harness
+pub const WALLET_LAMPORTS: u64 = 10 * LAMPORTS_PER_SOL;
+
+/// Saldo inicial de tokens de cada wallet em partidas SPL
+pub const WALLET_TOKENS: u64 = 10 * STAKE;
+
+/// Casas decimais dos mints de teste
+pub const DECIMALS: u8 = 9;
+
+/// Prazos padrão das partidas, relativos ao momento da criação (em segundos)
+pub const FUND_BY: i64 = 100;
+pub const BETS_CLOSE_AT: i64 = 100;
+pub const EARLIEST_RESOLUTION: i64 = 200;
+pub const RESOLVE_BY: i64 = 1_000;
+
+/// Prazo de saque padrão da Config, contado da resolução (em segundos)
+pub const CLAIM_WINDOW: i64 = 5_000;
+
+/// Motivo do `#[ignore]` dos testes de integração
+pub const NEEDS_BUILD: &str = "precisa do programa compilado: anchor build";
+
+/// ELF do programa: `$SBF_OUT_DIR/snake_betting.so` ou `target/deploy` do workspace
+fn program_elf() -> Vec<u8> {
+    let dir = std::env::var("SBF_OUT_DIR").unwrap_or_else(|_| {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/../../target/deploy").to_string()
+    });
+    let path = std::path::Path::new(&dir).join("snake_betting.so");
+
+    std::fs::read(&path).unwrap_or_else(|err| panic!("{} ({}): {err}", NEEDS_BUILD, path.display()))
+}
+
+/// Config padrão dos testes: sem taxas e com mínimos baixos
+pub fn default_config(treasury: Pubkey) -> ConfigArgs {
+    ConfigArgs {
+        treasury,
+        min_stake_lamports: 1_000,
+        min_bet_lamports: 1_000,
+        max_match_duration: 86_400,
+        protocol_fee_bps: 0,
+        max_fee_bps: 1_000,
+        claim_window: CLAIM_WINDOW,
+    }
+}
+
+/// Partida criada pelo harness, com os dois jogadores já com saldo
+pub struct TestMatch {
+    pub key: Pubkey,
+    pub player_a: Keypair,
+    pub player_b: Keypair,
+    pub spl: Option<SplMint>,
+}
+
+impl TestMatch {
+    /// Jogador de um lado
+    pub fn player(&self, side: Side) -> &Keypair {
+        match side {
+            Side::PlayerA => &self.player_a,
+            Side::PlayerB => &self.player_b,
+        }
+    }
+}
+
+/// Ambiente de teste: banco em processo, admin/tesouraria/árbitro já criados
+/// e a `Config` inicializada com [`default_config`].
+pub struct TestEnv {
+    pub ctx: ProgramTestContext,
+    pub admin: Keypair,
+    pub arbiter: Keypair,
+    pub treasury: Keypair,
+    sent: HashSet<Signature>,
+}
+
+impl TestEnv {
+    pub async fn new() -> Self {
+        let mut program_test = ProgramTest::default();
+        program_test.add_account(
+            snake_betting::ID,
+            Account {
+                lamports: LAMPORTS_PER_SOL,
+                data: program_elf(),
+                owner: bpf_loader::ID,
+                executable: true,
+                rent_epoch: 0,
+            },
+        );
+
+        let ctx = program_test.start_with_context().await;
+        let mut env = TestEnv {
+            ctx,
+            admin: Keypair::new(),
+            arbiter: Keypair::new(),
+            treasury: Keypair::new(),
+            sent: HashSet::new(),
+        };
+
+        for key in [
+            env.admin.pubkey(),
+            env.arbiter.pubkey(),
+            env.treasury.pubkey(),
+        ] {
+            env.airdrop(&key, WALLET_LAMPORTS).await;
+        }
+
+        let admin = env.admin.insecure_clone();
+        env.send(
+            &[instructions::initialize(
+                admin.pubkey(),
+                default_config(env.treasury.pubkey()),
+            )],
+            &[&admin],
+        )
+        .await
+        .unwrap();
+
+        env
+    }
+
+    /// Envia uma transação paga pelo payer do program-test.
+    ///
+    /// Transações idênticas têm a mesma assinatura e seriam descartadas como
+    /// repetidas, então nesse caso espera um blockhash novo antes de enviar.
+    pub async fn send(
+        &mut self,
+        ixs: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<(), TransactionError> {
+        let payer = self.ctx.payer.insecure_clone();
+        let mut all_signers = vec![&payer];
+        all_signers.extend_from_slice(signers);
+
+        let mut tx = Transaction::new_signed_with_payer(
+            ixs,
+            Some(&payer.pubkey()),
+            &all_signers,
+            self.ctx.last_blockhash,
+        );
+        if self.sent.contains(&tx.signatures[0]) {
+            self.ctx.last_blockhash = self.ctx.get_new_latest_blockhash().await.unwrap();
+            tx.sign(&all_signers, self.ctx.last_blockhash);
+        }
+        self.sent.insert(tx.signatures[0]);
+
+        self.ctx
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .map_err(|err| err.unwrap())
+    }
+
+    /// Transfere lamports do payer do program-test
+    pub async fn airdrop(&mut self, to: &Pubkey, lamports: u64) {
+        let payer = self.ctx.payer.pubkey();
+        self.send(&[system_instruction::transfer(&payer, to, lamports)], &[])
+            .await
+            .unwrap();
+    }
+
+    /// Wallet nova com saldo; em partidas SPL também ganha a ATA com tokens
+    pub async fn wallet(&mut self, spl: Option<SplMint>) -> Keypair {
+        let wallet = Keypair::new();
+        self.airdrop(&wallet.pubkey(), WALLET_LAMPORTS).await;
+
+        if let Some(spl) = spl {
+            self.create_token_account(spl, &wallet.pubkey()).await;
+            self.mint_to(spl, &wallet.pubkey(), WALLET_TOKENS).await;
+        }
+
+        wallet
+    }
+
+    // ---------------------------------------------------------------------
+    // Relógio e leitura de contas
+    // ---------------------------------------------------------------------
+
+    pub async fn clock(&mut self) -> Clock {
+        self.ctx.banks_client.get_sysvar::<Clock>().await.unwrap()
+    }
+
+    pub async fn now(&mut self) -> i64 {
+        self.clock().await.unix_timestamp
+    }
+
+    /// Muda o `unix_timestamp` do relógio (pra frente ou pra trás)
+    pub async fn warp_to(&mut self, unix_timestamp: i64) {
+        let mut clock = self.clock().await;
+        clock.unix_timestamp = unix_timestamp;
+        self.ctx.set_sysvar(&clock);
+    }
+
+    pub async fn warp_by(&mut self, seconds: i64) {
+        let now = self.now().await;
+        self.warp_to(now + seconds).await;
+    }
+
+    pub async fn balance(&mut self, key: &Pubkey) -> u64 {
+        self.ctx.banks_client.get_balance(*key).await.unwrap()
+    }
+
+    pub async fn exists(&mut self, key: &Pubkey) -> bool {
+        self.ctx
+            .banks_client
+            .get_account(*key)
+            .await
+            .unwrap()
+            .is_some()
+    }
+
+    async fn data(&mut self, key: &Pubkey) -> Vec<u8> {
+        self.ctx
+            .banks_client
+            .get_account(*key)
+            .await
+            .unwrap()
+            .unwrap_or_else(|| panic!("account {key} not found"))
+            .data
+    }
+
+    pub async fn config(&mut self) -> Config {
+        let data = self.data(&pda::config_address()).await;
+        accounts::decode_config(&data).unwrap()
+    }
+
+    pub async fn get_match(&mut self, key: &Pubkey) -> Match {
+        let data = self.data(key).await;
+        accounts::decode_match(&data).unwrap()
+    }
+
+    pub async fn participant(&mut self, m: &TestMatch, bettor: &Pubkey) -> Participant {
+        let data = self.data(&pda::participant_address(&m.key, bettor)).await;
+        accounts::decode_participant(&data).unwrap()
+    }
+
+    /// Lamports mínimos pra uma conta sem dados existir
+    pub async fn rent_floor(&mut self) -> u64 {
+        self.ctx
+            .banks_client
+            .get_rent()
+            .await
+            .unwrap()
+            .minimum_balance(0)
+    }
+
+    // ---------------------------------------------------------------------
+    // Tokens
+    // ---------------------------------------------------------------------
+
+    /// Cria um mint (SPL Token ou Token-2022); `transfer_fee_bps` só vale no
+    /// Token-2022 e liga a extensão de taxa de transferência.
+    pub async fn create_mint(
+        &mut self,
+        token_program: Pubkey,
+        transfer_fee_bps: Option<u16>,
+    ) -> SplMint {
+        let mint = Keypair::new();
+        let payer = self.ctx.payer.pubkey();
+
+        let extensions: &[ExtensionType] = if transfer_fee_bps.is_some() {
+            &[ExtensionType::TransferFeeConfig]
+        } else {
+            &[]
+        };
+        let space =
+            ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(extensions)
+                .unwrap();
+        let lamports = self
+            .ctx
+            .banks_client
+            .get_rent()
+            .await
+            .unwrap()
+            .minimum_balance(space);
+
+        let mut ixs = vec![system_instruction::create_account(
+            &payer,
+            &mint.pubkey(),
+            lamports,
+            space as u64,
+            &token_program,
+        )];
+        if let Some(bps) = transfer_fee_bps {
+            ixs.push(
+                transfer_fee::instruction::initialize_transfer_fee_config(
+                    &token_program,
+                    &mint.pubkey(),
+                    None,
+                    None,
+                    bps,
+                    u64::MAX,
+                )
+                .unwrap(),
+            );
+        }
+        ixs.push(
+            spl_token_2022::instruction::initialize_mint2(
+                &token_program,
+                &mint.pubkey(),
+                &payer,
+                None,
+                DECIMALS,
+            )
+            .unwrap(),
+        );
+
+        self.send(&ixs, &[&mint]).await.unwrap();
+
+        SplMint {
+            mint: mint.pubkey(),
+            token_program,
+        }
+    }
+
+    pub async fn create_token_account(&mut self, spl: SplMint, owner: &Pubkey) {
+        let payer = self.ctx.payer.pubkey();
+        let ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &payer,
+            owner,
+            &spl.mint,
+            &spl.token_program,
+        );
+        self.send(&[ix], &[]).await.unwrap();
+    }
+
+    pub async fn mint_to(&mut self, spl: SplMint, owner: &Pubkey, amount: u64) {
+        let payer = self.ctx.payer.pubkey();
+        let ix = spl_token_2022::instruction::mint_to(
+            &spl.token_program,
+            &spl.mint,
+            &spl.token_account(owner),
+            &payer,
+            &[],
+            amount,
+        )
+        .unwrap();
+        self.send(&[ix], &[]).await.unwrap();
+    }
+
+    /// Saldo de uma conta de token (SPL Token ou Token-2022)
+    pub async fn token_balance(&mut self, account: &Pubkey) -> u64 {
+        let data = self.data(account).await;
+        StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)
+            .unwrap()
+            .base
+            .amount
+    }
+
+    /// Saldo de tokens da ATA de `owner`
+    pub async fn tokens_of(&mut self, spl: SplMint, owner: &Pubkey) -> u64 {
+        self.token_balance(&spl.token_account(owner)).await
+    }
+
+    /// Taxa de transferência que o mint cobra sobre `amount` (0 sem a extensão)
+    pub async fn transfer_fee(&mut self, spl: SplMint, amount: u64) -> u64 {
+        let data = self.data(&spl.mint).await;
+        let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data).unwrap();
+        match mint.get_extension::<transfer_fee::TransferFeeConfig>() {
+            Ok(config) => config.calculate_epoch_fee(0, amount).unwrap(),
+            Err(_) => 0,
+        }
+    }
+
+    /// Mint SPL Token clássico, com ATAs da tesouraria e do árbitro criadas
+    pub async fn spl_token_mint(&mut self) -> SplMint {
+        let spl = self.create_mint(anchor_spl::token::ID, None).await;
+        self.create_protocol_token_accounts(spl).await;
+        spl
+    }
+
+    /// Mint Token-2022 (opcionalmente com taxa de transferência), com ATAs da
+    /// tesouraria e do árbitro criadas
+    pub async fn token_2022_mint(&mut self, transfer_fee_bps: Option<u16>) -> SplMint {
+        let spl = self
+            .create_mint(anchor_spl::token_2022::ID, transfer_fee_bps)
+            .await;
+        self.create_protocol_token_accounts(spl).await;
+        spl
+    }
+
+    async fn create_protocol_token_accounts(&mut self, spl: SplMint) {
+        let treasury = self.treasury.pubkey();
+        let arbiter = self.arbiter.pubkey();
+        self.create_token_account(spl, &treasury).await;
+        self.create_token_account(spl, &arbiter).await;
+    }
+
+    // ---------------------------------------------------------------------
+    // Config e partidas
+    // ---------------------------------------------------------------------
+
+    /// `update_config` assinado pelo admin, partindo da config padrão
+    pub async fn update_config(&mut self, f: impl FnOnce(&mut ConfigArgs)) {
+        let mut args = default_config(self.treasury.pubkey());
+        f(&mut args);
+
+        let admin = self.admin.insecure_clone();
+        self.send(
+            &[instructions::update_config(admin.pubkey(), args)],
+            &[&admin],
+        )
+        .await
+        .unwrap();
+    }
+
+    /// `set_paused` assinado pelo admin
+    pub async fn set_paused(&mut self, paused: bool, withdrawals_paused: bool) {
+        let admin = self.admin.insecure_clone();
+        self.send(
+            &[instructions::set_paused(
+                admin.pubkey(),
+                paused,
+                withdrawals_paused,
+            )],
+            &[&admin],
+        )
+        .await
+        .unwrap();
+    }
+
+    /// `set_match_suspended` assinado por `authority` (árbitro ou admin)
+    pub async fn set_match_suspended(
+        &mut self,
+        m: &TestMatch,
+        authority: &Keypair,
+        suspended: bool,
+        withdrawals_suspended: bool,
+    ) -> Result<(), TransactionError> {
+        self.send(
+            &[instructions::set_match_suspended(
+                m.key,
+                authority.pubkey(),
+                suspended,
+                withdrawals_suspended,
+            )],
+            &[authority],
+        )
+        .await
+    }
+
+    /// Argumentos padrão de uma partida em SOL criada agora
+    pub async fn match_args(&mut self, player_a: Pubkey, player_b: Pubkey) -> CreateMatchArgs {
+        let counter = pda::match_counter_address(&self.arbiter.pubkey());
+        let id = if self.exists(&counter).await {
+            let data = self.data(&counter).await;
+            accounts::decode_match_counter(&data).unwrap().next_id
+        } else {
+            0
+        };
+        let now = self.now().await;
+
+        CreateMatchArgs {
+            id,
+            stake_lamports: STAKE,
+            fund_by: now + FUND_BY,
+            bets_close_at: now + BETS_CLOSE_AT,
+            earliest_resolution: now + EARLIEST_RESOLUTION,
+            resolve_by: now + RESOLVE_BY,
+            player_a,
+            player_b,
+            unmatched_pool_policy: UnmatchedPoolPolicy::RefundBettors,
+            arbiter_fee_bps: 0,
+        }
+    }
+
+    /// Cria o contador do árbitro, se ainda não existir
+    pub async fn ensure_match_counter(&mut self) {
+        let arbiter = self.arbiter.insecure_clone();
+        if !self
+            .exists(&pda::match_counter_address(&arbiter.pubkey()))
+            .await
+        {
+            self.send(
+                &[instructions::init_match_counter(arbiter.pubkey())],
+                &[&arbiter],
+            )
+            .await
+            .unwrap();
+        }
+    }
+
+    /// Envia `create_match` com os argumentos padrão ajustados por `f`
+    pub async fn try_create_match(
+        &mut self,
+        spl: Option<SplMint>,
+        f: impl FnOnce(&mut CreateMatchArgs),
+    ) -> Result<TestMatch, TransactionError> {
+        self.ensure_match_counter().await;
+
+        let player_a = self.wallet(spl).await;
+        let player_b = self.wallet(spl).await;
+        let mut args = self.match_args(player_a.pubkey(), player_b.pubkey()).await;
+        f(&mut args);
+
+        let arbiter = self.arbiter.insecure_clone();
+        let key = pda::match_address(&arbiter.pubkey(), args.id);
+        self.send(
+            &[instructions::create_match(arbiter.pubkey(), args, spl)],
+            &[&arbiter],
+        )
+        .await?;
+
+        Ok(TestMatch {
+            key,
+            player_a,
+            player_b,
+            spl,
+        })
+    }
+
+    /// Partida em SOL com os argumentos padrão
+    pub async fn create_match(&mut self) -> TestMatch {
+        self.try_create_match(None, |_| {}).await.unwrap()
+    }
+
+    /// Partida em SOL com os argumentos padrão ajustados por `f`
+    pub async fn create_match_with(&mut self, f: impl FnOnce(&mut CreateMatchArgs)) -> TestMatch {
+        self.try_create_match(None, f).await.unwrap()
+    }
+
+    pub async fn join(&mut self, m: &TestMatch, player: &Keypair) -> Result<(), TransactionError> {
+        self.send(
+            &[instructions::join_as_player(m.key, player.pubkey(), m.spl)],
+            &[player],
+        )
+        .await
+    }
+
+    /// Os dois jogadores depositam: a partida fica `Funded`
+    pub async fn fund(&mut self, m: &TestMatch) {
+        self.join(m, &m.player_a).await.unwrap();
+        self.join(m, &m.player_b).await.unwrap();
+    }
+
+    pub async fn bet(
+        &mut self,
+        m: &TestMatch,
+        bettor: &Keypair,
+        side: Side,
+        amount: u64,
+    ) -> Result<(), TransactionError> {
+        self.send(
+            &[instructions::place_bet(
+                m.key,
+                bettor.pubkey(),
+                side,
+                amount,
+                m.spl,
+            )],
+            &[bettor],
+        )
+        .await
+    }
+
+    /// Wallet nova que já aposta `amount` em `side`
+    pub async fn bettor(&mut self, m: &TestMatch, side: Side, amount: u64) -> Keypair {
+        let bettor = self.wallet(m.spl).await;
+        self.bet(m, &bettor, side, amount).await.unwrap();
+        bettor
+    }
+
+    pub async fn start(&mut self, m: &TestMatch) -> Result<(), TransactionError> {
+        let arbiter = self.arbiter.insecure_clone();
+        self.send(
+            &[instructions::start_match(m.key, arbiter.pubkey())],
+            &[&arbiter],
+        )
+        .await
+    }
+
+    pub async fn declare_winner(
+        &mut self,
+        m: &TestMatch,
+        winner: Side,
+    ) -> Result<(), TransactionError> {
+        let arbiter = self.arbiter.insecure_clone();
+        let treasury = self.treasury.pubkey();
+        self.send(
+            &[instructions::declare_winner(
+                m.key,
+                arbiter.pubkey(),
+                treasury,
+                winner,
+                m.spl,
+            )],
+            &[&arbiter],
+        )
+        .await
+    }
+
+    pub async fn declare_draw(&mut self, m: &TestMatch) -> Result<(), TransactionError> {
+        let arbiter = self.arbiter.insecure_clone();
+        self.send(
+            &[instructions::declare_draw(m.key, arbiter.pubkey())],
+            &[&arbiter],
+        )
+        .await
+    }
+
+    pub async fn declare_walkover(&mut self, m: &TestMatch) -> Result<(), TransactionError> {
+        let arbiter = self.arbiter.insecure_clone();
+        self.send(
+            &[instructions::declare_walkover(m.key, arbiter.pubkey())],
+            &[&arbiter],
+        )
+        .await
+    }
+
+    pub async fn cancel(&mut self, m: &TestMatch) -> Result<(), TransactionError> {
+        let arbiter = self.arbiter.insecure_clone();
+        self.send(
+            &[instructions::cancel_match(m.key, arbiter.pubkey())],
+            &[&arbiter],
+        )
+        .await
+    }
+
+    /// Inicia a partida e avança o relógio até `earliest_resolution`
+    pub async fn start_and_wait(&mut self, m: &TestMatch) {
+        self.start(m).await.unwrap();
+        let earliest_resolution = self.get_match(&m.key).await.earliest_resolution;
+        self.warp_to(earliest_resolution).await;
+    }
+
+    /// Inicia, espera e declara o vencedor
+    pub async fn resolve(&mut self, m: &TestMatch, winner: Side) {
+        self.start_and_wait(m).await;
+        self.declare_winner(m, winner).await.unwrap();
+    }
+
+    pub async fn withdraw_winner_stake(
+        &mut self,
+        m: &TestMatch,
+        winner: &Keypair,
+    ) -> Result<(), TransactionError> {
+        self.send(
+            &[instructions::withdraw_winner_stake(
+                m.key,
+                winner.pubkey(),
+                m.spl,
+            )],
+            &[winner],
+        )
+        .await
+    }
+
+    pub async fn claim_bet_payout(
+        &mut self,
+        m: &TestMatch,
+        bettor: &Keypair,
+    ) -> Result<(), TransactionError> {
+        self.send(
+            &[instructions::claim_bet_payout(
+                m.key,
+                bettor.pubkey(),
+                m.spl,
+            )],
+            &[bettor],
+        )
+        .await
+    }
+
+    /// `refund_player`, `refund_bet`, `expire_match`, `close_participant`,
+    /// `claim_unmatched_pool`, `sweep_dust` e `close_match` não exigem
+    /// assinatura: o payer envia.
+    pub async fn refund_player(
+        &mut self,
+        m: &TestMatch,
+        player: &Pubkey,
+    ) -> Result<(), TransactionError> {
+        self.send(&[instructions::refund_player(m.key, *player, m.spl)], &[])
+            .await
+    }
+
+    pub async fn refund_bet(
+        &mut self,
+        m: &TestMatch,
+        bettor: &Pubkey,
+    ) -> Result<(), TransactionError> {
+        self.send(&[instructions::refund_bet(m.key, *bettor, m.spl)], &[])
+            .await
+    }
+
+    pub async fn expire(&mut self, m: &TestMatch) -> Result<(), TransactionError> {
+        self.send(&[instructions::expire_match(m.key)], &[]).await
+    }
+
+    pub async fn claim_unmatched_pool(
+        &mut self,
+        m: &TestMatch,
+        recipient: &Pubkey,
+    ) -> Result<(), TransactionError> {
+        self.send(
+            &[instructions::claim_unmatched_pool(m.key, *recipient, m.spl)],
+            &[],
+        )
+        .await
+    }
+
+    pub async fn sweep_dust(
+        &mut self,
+        m: &TestMatch,
+        recipient: &Pubkey,
+    ) -> Result<(), TransactionError> {
+        self.send(&[instructions::sweep_dust(m.key, *recipient, m.spl)], &[])
+            .await
+    }
+
+    /// `sweep_unclaimed` assinado por `authority` (árbitro ou admin)
+    pub async fn sweep_unclaimed(
+        &mut self,
+        m: &TestMatch,
+        authority: &Keypair,
+    ) -> Result<(), TransactionError> {
+        let treasury = self.treasury.pubkey();
+        self.send(
+            &[instructions::sweep_unclaimed(
+                m.key,
+                authority.pubkey(),
+                treasury,
+                m.spl,
+            )],
+            &[authority],
+        )
+        .await
+    }
+
+    pub async fn close_participant(
+        &mut self,
+        m: &TestMatch,
+        bettor: &Pubkey,
+    ) -> Result<(), TransactionError> {
+        self.send(&[instructions::close_participant(m.key, *bettor)], &[])
+            .await
+    }
+
+    pub async fn close_match(&mut self, m: &TestMatch) -> Result<(), TransactionError> {
+        let arbiter = self.arbiter.pubkey();
+        self.send(&[instructions::close_match(m.key, arbiter, m.spl)], &[])
+            .await
+    }
+}
+
+/// Confere que a transação falhou com o `CustomError` esperado
+#[track_caller]
+pub fn assert_custom_error(result: Result<(), TransactionError>, expected: CustomError) {
+    match result {
+        Err(TransactionError::InstructionError(_, InstructionError::Custom(code)))
+            if code == u32::from(expected) => {}
+        other => panic!(
+            "expected {expected:?} ({}), got {other:?}",
+            u32::from(expected)
+        ),
+    }
+}
+
+/// Lado oposto
+pub fn other_side(side: Side) -> Side {
+    match side {
+        Side::PlayerA => Side::PlayerB,
+        Side::PlayerB => Side::PlayerA,
+    }
+}
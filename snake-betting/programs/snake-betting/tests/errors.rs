@@ -0,0 +1,1229 @@
+//! Um teste por caminho de `CustomError` alcançável pelas instruções.
+//!
+//! Ficam de fora os erros defensivos, que as validações anteriores (ou as
+//! seeds das contas) já impedem de acontecer:
+//! - `MathOverflow`: valores limitados pelo saldo das wallets.
+//! - `NoWinner`: toda partida `Resolved`/`Walkover` tem vencedor.
+//! - `NotBettor`: a PDA da participação já é derivada do `bettor`.
+//! - `NoBetsOnWinnerSide` no cálculo do payout: quem chega lá apostou no
+//!   vencedor, então o total do lado vencedor é > 0 (ver
+//!   [`no_bets_on_winner_side_never_reaches_the_payout`]). O erro só sai
+//!   do `sweep_dust` ([`no_bets_on_winner_side`]).
+//! - `EscrowInvariantViolated`: o vault só é movimentado pelo programa.
+
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::*;
+use snake_betting_client::{instructions, CustomError, Side, UnmatchedPoolPolicy};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::Signer;
+
+// ---------------------------------------------------------------------------
+// Config
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn not_admin() {
+    let mut env = TestEnv::new().await;
+    let intruder = env.wallet(None).await;
+    let args = default_config(intruder.pubkey());
+
+    let result = env
+        .send(
+            &[instructions::update_config(intruder.pubkey(), args)],
+            &[&intruder],
+        )
+        .await;
+    assert_custom_error(result, CustomError::NotAdmin);
+
+    let result = env
+        .send(
+            &[instructions::set_admin(
+                intruder.pubkey(),
+                intruder.pubkey(),
+            )],
+            &[&intruder],
+        )
+        .await;
+    assert_custom_error(result, CustomError::NotAdmin);
+
+    let result = env
+        .send(
+            &[instructions::set_paused(intruder.pubkey(), true, true)],
+            &[&intruder],
+        )
+        .await;
+    assert_custom_error(result, CustomError::NotAdmin);
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn invalid_config() {
+    let mut env = TestEnv::new().await;
+    let admin = env.admin.insecure_clone();
+
+    let mut args = default_config(env.treasury.pubkey());
+    args.max_match_duration = 0;
+    let result = env
+        .send(
+            &[instructions::update_config(admin.pubkey(), args)],
+            &[&admin],
+        )
+        .await;
+    assert_custom_error(result, CustomError::InvalidConfig);
+
+    let mut args = default_config(env.treasury.pubkey());
+    args.claim_window = 0;
+    let result = env
+        .send(
+            &[instructions::update_config(admin.pubkey(), args)],
+            &[&admin],
+        )
+        .await;
+    assert_custom_error(result, CustomError::InvalidConfig);
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn fee_too_high() {
+    let mut env = TestEnv::new().await;
+    let admin = env.admin.insecure_clone();
+
+    // teto acima do teto absoluto
+    let mut args = default_config(env.treasury.pubkey());
+    args.max_fee_bps = snake_betting::MAX_FEE_BPS + 1;
+    let result = env
+        .send(
+            &[instructions::update_config(admin.pubkey(), args)],
+            &[&admin],
+        )
+        .await;
+    assert_custom_error(result, CustomError::FeeTooHigh);
+
+    // taxa do protocolo + taxa do árbitro acima do teto da Config
+    let max_fee_bps = env.config().await.max_fee_bps;
+    let result = env
+        .try_create_match(None, |args| args.arbiter_fee_bps = max_fee_bps + 1)
+        .await;
+    assert_custom_error(result.map(|_| ()), CustomError::FeeTooHigh);
+}
+
+// ---------------------------------------------------------------------------
+// create_match
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn invalid_deadline() {
+    let mut env = TestEnv::new().await;
+    let now = env.now().await;
+
+    let result = env
+        .try_create_match(None, |args| {
+            args.fund_by = now;
+            args.bets_close_at = now;
+        })
+        .await;
+    assert_custom_error(result.map(|_| ()), CustomError::InvalidDeadline);
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn invalid_fund_by() {
+    let mut env = TestEnv::new().await;
+    let now = env.now().await;
+
+    // depois do fechamento das apostas
+    let result = env
+        .try_create_match(None, |args| args.fund_by = args.bets_close_at + 1)
+        .await;
+    assert_custom_error(result.map(|_| ()), CustomError::InvalidFundBy);
+
+    // no passado
+    let result = env.try_create_match(None, |args| args.fund_by = now).await;
+    assert_custom_error(result.map(|_| ()), CustomError::InvalidFundBy);
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn invalid_earliest_resolution() {
+    let mut env = TestEnv::new().await;
+
+    let result = env
+        .try_create_match(None, |args| {
+            args.earliest_resolution = args.bets_close_at - 1;
+        })
+        .await;
+    assert_custom_error(result.map(|_| ()), CustomError::InvalidEarliestResolution);
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn invalid_resolve_by() {
+    let mut env = TestEnv::new().await;
+
+    let result = env
+        .try_create_match(None, |args| args.resolve_by = args.earliest_resolution)
+        .await;
+    assert_custom_error(result.map(|_| ()), CustomError::InvalidResolveBy);
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn invalid_stake() {
+    let mut env = TestEnv::new().await;
+
+    let result = env
+        .try_create_match(None, |args| args.stake_lamports = 0)
+        .await;
+    assert_custom_error(result.map(|_| ()), CustomError::InvalidStake);
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn stake_below_minimum() {
+    let mut env = TestEnv::new().await;
+    let min_stake = env.config().await.min_stake_lamports;
+
+    let result = env
+        .try_create_match(None, |args| args.stake_lamports = min_stake - 1)
+        .await;
+    assert_custom_error(result.map(|_| ()), CustomError::StakeBelowMinimum);
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn match_too_long() {
+    let mut env = TestEnv::new().await;
+    let max_duration = env.config().await.max_match_duration;
+    let now = env.now().await;
+
+    let result = env
+        .try_create_match(None, |args| args.resolve_by = now + max_duration + 1)
+        .await;
+    assert_custom_error(result.map(|_| ()), CustomError::MatchTooLong);
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn match_id_mismatch() {
+    let mut env = TestEnv::new().await;
+
+    let result = env.try_create_match(None, |args| args.id += 1).await;
+    assert_custom_error(result.map(|_| ()), CustomError::MatchIdMismatch);
+}
+
+// ---------------------------------------------------------------------------
+// join_as_player
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn not_a_player() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    let intruder = env.wallet(None).await;
+
+    assert_custom_error(env.join(&m, &intruder).await, CustomError::NotAPlayer);
+
+    // refund_player também só aceita os dois jogadores
+    env.cancel(&m).await.unwrap();
+    assert_custom_error(
+        env.refund_player(&m, &intruder.pubkey()).await,
+        CustomError::NotAPlayer,
+    );
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn already_deposited() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+
+    env.join(&m, &m.player_a).await.unwrap();
+    assert_custom_error(
+        env.join(&m, &m.player_a).await,
+        CustomError::AlreadyDeposited,
+    );
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn funding_closed() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+
+    let fund_by = env.get_match(&m.key).await.fund_by;
+    env.warp_to(fund_by).await;
+    assert_custom_error(env.join(&m, &m.player_a).await, CustomError::FundingClosed);
+}
+
+// ---------------------------------------------------------------------------
+// place_bet
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn invalid_amount() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    let bettor = env.wallet(None).await;
+
+    assert_custom_error(
+        env.bet(&m, &bettor, Side::PlayerA, 0).await,
+        CustomError::InvalidAmount,
+    );
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn bet_below_minimum() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    let bettor = env.wallet(None).await;
+    let min_bet = env.config().await.min_bet_lamports;
+
+    assert_custom_error(
+        env.bet(&m, &bettor, Side::PlayerA, min_bet - 1).await,
+        CustomError::BetBelowMinimum,
+    );
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn side_mismatch() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    let bettor = env.bettor(&m, Side::PlayerA, STAKE).await;
+
+    assert_custom_error(
+        env.bet(&m, &bettor, Side::PlayerB, STAKE).await,
+        CustomError::SideMismatch,
+    );
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn bets_closed_after_bets_close_at() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    let bettor = env.wallet(None).await;
+
+    let bets_close_at = env.get_match(&m.key).await.bets_close_at;
+    env.warp_to(bets_close_at - 1).await;
+    env.bet(&m, &bettor, Side::PlayerA, STAKE).await.unwrap();
+
+    env.warp_to(bets_close_at).await;
+    assert_custom_error(
+        env.bet(&m, &bettor, Side::PlayerA, STAKE).await,
+        CustomError::BetsClosed,
+    );
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn bets_closed_once_the_match_started() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    let bettor = env.wallet(None).await;
+
+    env.start(&m).await.unwrap();
+    assert_custom_error(
+        env.bet(&m, &bettor, Side::PlayerA, STAKE).await,
+        CustomError::BetsClosed,
+    );
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn bets_closed_when_funding_expired() {
+    let mut env = TestEnv::new().await;
+    let m = env
+        .create_match_with(|args| args.fund_by = args.bets_close_at - 50)
+        .await;
+    env.join(&m, &m.player_a).await.unwrap();
+    let bettor = env.wallet(None).await;
+
+    // apostas ainda abertas, mas a partida não foi financiada a tempo
+    let fund_by = env.get_match(&m.key).await.fund_by;
+    env.warp_to(fund_by).await;
+    assert_custom_error(
+        env.bet(&m, &bettor, Side::PlayerA, STAKE).await,
+        CustomError::BetsClosed,
+    );
+}
+
+// ---------------------------------------------------------------------------
+// start_match / declare_winner / declare_draw
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn not_arbiter() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    let intruder = env.wallet(None).await;
+
+    let result = env
+        .send(
+            &[instructions::start_match(m.key, intruder.pubkey())],
+            &[&intruder],
+        )
+        .await;
+    assert_custom_error(result, CustomError::NotArbiter);
+
+    env.start_and_wait(&m).await;
+    let treasury = env.treasury.pubkey();
+    for ix in [
+        instructions::declare_winner(m.key, intruder.pubkey(), treasury, Side::PlayerA, None),
+        instructions::declare_draw(m.key, intruder.pubkey()),
+        instructions::declare_walkover(m.key, intruder.pubkey()),
+        instructions::cancel_match(m.key, intruder.pubkey()),
+    ] {
+        let result = env.send(&[ix], &[&intruder]).await;
+        assert_custom_error(result, CustomError::NotArbiter);
+    }
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn match_not_funded() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.join(&m, &m.player_a).await.unwrap();
+
+    assert_custom_error(env.start(&m).await, CustomError::MatchNotFunded);
+
+    let earliest_resolution = env.get_match(&m.key).await.earliest_resolution;
+    env.warp_to(earliest_resolution).await;
+    assert_custom_error(
+        env.declare_winner(&m, Side::PlayerA).await,
+        CustomError::MatchNotFunded,
+    );
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn match_not_started() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.fund(&m).await;
+
+    let earliest_resolution = env.get_match(&m.key).await.earliest_resolution;
+    env.warp_to(earliest_resolution).await;
+    assert_custom_error(
+        env.declare_winner(&m, Side::PlayerA).await,
+        CustomError::MatchNotStarted,
+    );
+    assert_custom_error(env.declare_draw(&m).await, CustomError::MatchNotStarted);
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn too_early() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    env.start(&m).await.unwrap();
+
+    let earliest_resolution = env.get_match(&m.key).await.earliest_resolution;
+    env.warp_to(earliest_resolution - 1).await;
+    assert_custom_error(
+        env.declare_winner(&m, Side::PlayerA).await,
+        CustomError::TooEarly,
+    );
+    assert_custom_error(env.declare_draw(&m).await, CustomError::TooEarly);
+
+    env.warp_to(earliest_resolution).await;
+    env.declare_winner(&m, Side::PlayerA).await.unwrap();
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn resolution_expired() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.fund(&m).await;
+
+    let resolve_by = env.get_match(&m.key).await.resolve_by;
+    env.warp_to(resolve_by).await;
+    assert_custom_error(env.start(&m).await, CustomError::ResolutionExpired);
+
+    env.warp_to(resolve_by - 1).await;
+    env.start(&m).await.unwrap();
+    env.warp_to(resolve_by).await;
+    assert_custom_error(
+        env.declare_winner(&m, Side::PlayerA).await,
+        CustomError::ResolutionExpired,
+    );
+
+    // W.O. também não vale depois do prazo de resolução
+    let m = env.create_match().await;
+    env.join(&m, &m.player_a).await.unwrap();
+    let resolve_by = env.get_match(&m.key).await.resolve_by;
+    env.warp_to(resolve_by).await;
+    assert_custom_error(
+        env.declare_walkover(&m).await,
+        CustomError::ResolutionExpired,
+    );
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn already_resolved() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    env.resolve(&m, Side::PlayerA).await;
+
+    assert_custom_error(
+        env.declare_winner(&m, Side::PlayerA).await,
+        CustomError::AlreadyResolved,
+    );
+    assert_custom_error(env.declare_draw(&m).await, CustomError::AlreadyResolved);
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn wrong_recipient() {
+    let mut env = TestEnv::new().await;
+
+    // declare_winner com uma conta que não é a tesouraria da Config
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    env.start_and_wait(&m).await;
+    let arbiter = env.arbiter.insecure_clone();
+    let fake_treasury = Pubkey::new_unique();
+    let result = env
+        .send(
+            &[instructions::declare_winner(
+                m.key,
+                arbiter.pubkey(),
+                fake_treasury,
+                Side::PlayerA,
+                None,
+            )],
+            &[&arbiter],
+        )
+        .await;
+    assert_custom_error(result, CustomError::WrongRecipient);
+
+    // claim_unmatched_pool pra outro destino que não o da política
+    let m = env
+        .create_match_with(|args| args.unmatched_pool_policy = UnmatchedPoolPolicy::Treasury)
+        .await;
+    env.fund(&m).await;
+    env.bettor(&m, Side::PlayerB, STAKE).await;
+    env.resolve(&m, Side::PlayerA).await;
+    assert_custom_error(
+        env.claim_unmatched_pool(&m, &m.player_a.pubkey()).await,
+        CustomError::WrongRecipient,
+    );
+
+    // sweep_dust pra outro destino que não o da política
+    let m = env
+        .create_match_with(|args| args.unmatched_pool_policy = UnmatchedPoolPolicy::WinningPlayer)
+        .await;
+    env.fund(&m).await;
+    let winner = env.bettor(&m, Side::PlayerA, STAKE).await;
+    env.resolve(&m, Side::PlayerA).await;
+    env.claim_bet_payout(&m, &winner).await.unwrap();
+    let treasury = env.treasury.pubkey();
+    assert_custom_error(
+        env.sweep_dust(&m, &treasury).await,
+        CustomError::WrongRecipient,
+    );
+
+    // sweep_unclaimed pra outra conta que não a tesouraria
+    env.warp_by(CLAIM_WINDOW).await;
+    let result = env
+        .send(
+            &[instructions::sweep_unclaimed(
+                m.key,
+                arbiter.pubkey(),
+                fake_treasury,
+                None,
+            )],
+            &[&arbiter],
+        )
+        .await;
+    assert_custom_error(result, CustomError::WrongRecipient);
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn invalid_status() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.fund(&m).await;
+
+    // start_match duas vezes
+    env.start(&m).await.unwrap();
+    assert_custom_error(env.start(&m).await, CustomError::InvalidStatus);
+
+    // cancelar uma partida já cancelada
+    env.cancel(&m).await.unwrap();
+    assert_custom_error(env.cancel(&m).await, CustomError::InvalidStatus);
+
+    // partida cancelada não tem vencedor pra sacar
+    assert_custom_error(
+        env.withdraw_winner_stake(&m, &m.player_a).await,
+        CustomError::InvalidStatus,
+    );
+
+    // partida em andamento não devolve stakes nem apostas
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    let bettor = env.bettor(&m, Side::PlayerA, STAKE).await;
+    assert_custom_error(
+        env.refund_player(&m, &m.player_a.pubkey()).await,
+        CustomError::InvalidStatus,
+    );
+    assert_custom_error(
+        env.refund_bet(&m, &bettor.pubkey()).await,
+        CustomError::InvalidStatus,
+    );
+    assert_custom_error(
+        env.claim_bet_payout(&m, &bettor).await,
+        CustomError::InvalidStatus,
+    );
+    let treasury = env.treasury.pubkey();
+    assert_custom_error(
+        env.sweep_dust(&m, &treasury).await,
+        CustomError::InvalidStatus,
+    );
+
+    // partida cancelada não tem prazo de saque pra vencer
+    env.cancel(&m).await.unwrap();
+    env.warp_by(CLAIM_WINDOW).await;
+    let arbiter = env.arbiter.insecure_clone();
+    assert_custom_error(
+        env.sweep_unclaimed(&m, &arbiter).await,
+        CustomError::InvalidStatus,
+    );
+}
+
+// ---------------------------------------------------------------------------
+// W.O.
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn walkover_not_allowed() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+
+    let fund_by = env.get_match(&m.key).await.fund_by;
+    env.warp_to(fund_by).await;
+    assert_custom_error(
+        env.declare_walkover(&m).await,
+        CustomError::WalkoverNotAllowed,
+    );
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn funding_not_closed() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.join(&m, &m.player_a).await.unwrap();
+
+    let result = env
+        .send(
+            &[instructions::claim_walkover(m.key, m.player_a.pubkey())],
+            &[&m.player_a],
+        )
+        .await;
+    assert_custom_error(result, CustomError::FundingNotClosed);
+
+    // nem o árbitro declara W.O. antes do prazo de depósito
+    assert_custom_error(
+        env.declare_walkover(&m).await,
+        CustomError::FundingNotClosed,
+    );
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn not_winner_player() {
+    let mut env = TestEnv::new().await;
+
+    // o perdedor tenta sacar os stakes
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    env.resolve(&m, Side::PlayerA).await;
+    assert_custom_error(
+        env.withdraw_winner_stake(&m, &m.player_b).await,
+        CustomError::NotWinnerPlayer,
+    );
+
+    // quem não depositou tenta reivindicar o W.O.
+    let m = env.create_match().await;
+    env.join(&m, &m.player_a).await.unwrap();
+    let fund_by = env.get_match(&m.key).await.fund_by;
+    env.warp_to(fund_by).await;
+    let result = env
+        .send(
+            &[instructions::claim_walkover(m.key, m.player_b.pubkey())],
+            &[&m.player_b],
+        )
+        .await;
+    assert_custom_error(result, CustomError::NotWinnerPlayer);
+}
+
+// ---------------------------------------------------------------------------
+// Saques e reembolsos
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn stakes_already_withdrawn() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    env.resolve(&m, Side::PlayerB).await;
+
+    env.withdraw_winner_stake(&m, &m.player_b).await.unwrap();
+    assert_custom_error(
+        env.withdraw_winner_stake(&m, &m.player_b).await,
+        CustomError::StakesAlreadyWithdrawn,
+    );
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn wrong_side() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    env.bettor(&m, Side::PlayerA, STAKE).await;
+    let loser = env.bettor(&m, Side::PlayerB, STAKE).await;
+    env.resolve(&m, Side::PlayerA).await;
+
+    assert_custom_error(
+        env.claim_bet_payout(&m, &loser).await,
+        CustomError::WrongSide,
+    );
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn already_claimed() {
+    let mut env = TestEnv::new().await;
+
+    // prêmio sacado duas vezes
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    let winner = env.bettor(&m, Side::PlayerA, STAKE).await;
+    env.resolve(&m, Side::PlayerA).await;
+    env.claim_bet_payout(&m, &winner).await.unwrap();
+    assert_custom_error(
+        env.claim_bet_payout(&m, &winner).await,
+        CustomError::AlreadyClaimed,
+    );
+
+    // aposta reembolsada duas vezes
+    let m = env.create_match().await;
+    let bettor = env.bettor(&m, Side::PlayerA, STAKE).await;
+    env.cancel(&m).await.unwrap();
+    env.refund_bet(&m, &bettor.pubkey()).await.unwrap();
+    assert_custom_error(
+        env.refund_bet(&m, &bettor.pubkey()).await,
+        CustomError::AlreadyClaimed,
+    );
+
+    // pool sem apostas no vencedor sacado duas vezes
+    let m = env
+        .create_match_with(|args| args.unmatched_pool_policy = UnmatchedPoolPolicy::Treasury)
+        .await;
+    env.fund(&m).await;
+    env.bettor(&m, Side::PlayerB, STAKE).await;
+    env.resolve(&m, Side::PlayerA).await;
+    let treasury = env.treasury.pubkey();
+    env.claim_unmatched_pool(&m, &treasury).await.unwrap();
+    assert_custom_error(
+        env.claim_unmatched_pool(&m, &treasury).await,
+        CustomError::AlreadyClaimed,
+    );
+
+    // sobra de arredondamento varrida duas vezes
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    let winner = env.bettor(&m, Side::PlayerA, STAKE).await;
+    env.resolve(&m, Side::PlayerA).await;
+    env.claim_bet_payout(&m, &winner).await.unwrap();
+    env.sweep_dust(&m, &treasury).await.unwrap();
+    assert_custom_error(
+        env.sweep_dust(&m, &treasury).await,
+        CustomError::AlreadyClaimed,
+    );
+
+    // o que não foi sacado varrido duas vezes
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    env.resolve(&m, Side::PlayerA).await;
+    env.warp_by(CLAIM_WINDOW).await;
+    let arbiter = env.arbiter.insecure_clone();
+    env.sweep_unclaimed(&m, &arbiter).await.unwrap();
+    assert_custom_error(
+        env.sweep_unclaimed(&m, &arbiter).await,
+        CustomError::AlreadyClaimed,
+    );
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn payouts_pending() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    let first = env.bettor(&m, Side::PlayerA, STAKE).await;
+    env.bettor(&m, Side::PlayerA, STAKE).await;
+    env.resolve(&m, Side::PlayerA).await;
+
+    // um dos dois vencedores ainda não sacou
+    env.claim_bet_payout(&m, &first).await.unwrap();
+    let treasury = env.treasury.pubkey();
+    assert_custom_error(
+        env.sweep_dust(&m, &treasury).await,
+        CustomError::PayoutsPending,
+    );
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn no_bets_on_winner_side() {
+    let mut env = TestEnv::new().await;
+    let m = env
+        .create_match_with(|args| args.unmatched_pool_policy = UnmatchedPoolPolicy::Treasury)
+        .await;
+    env.fund(&m).await;
+    env.bettor(&m, Side::PlayerB, STAKE).await;
+    env.resolve(&m, Side::PlayerA).await;
+
+    // sem vencedores não tem sobra: o pool inteiro sai pelo claim_unmatched_pool
+    let treasury = env.treasury.pubkey();
+    assert_custom_error(
+        env.sweep_dust(&m, &treasury).await,
+        CustomError::NoBetsOnWinnerSide,
+    );
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn no_bets_on_winner_side_never_reaches_the_payout() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    let bettor = env.bettor(&m, Side::PlayerB, STAKE).await;
+    env.resolve(&m, Side::PlayerA).await;
+
+    // Ninguém apostou no vencedor: o único apostador é barrado antes do cálculo
+    // (`WrongSide`) e o pool segue a política da partida.
+    assert_eq!(env.get_match(&m.key).await.total_side_a, 0);
+    assert_custom_error(
+        env.claim_bet_payout(&m, &bettor).await,
+        CustomError::WrongSide,
+    );
+    env.refund_bet(&m, &bettor.pubkey()).await.unwrap();
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn nothing_to_refund() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.join(&m, &m.player_a).await.unwrap();
+    env.cancel(&m).await.unwrap();
+
+    assert_custom_error(
+        env.refund_player(&m, &m.player_b.pubkey()).await,
+        CustomError::NothingToRefund,
+    );
+
+    // stake já devolvido
+    env.refund_player(&m, &m.player_a.pubkey()).await.unwrap();
+    assert_custom_error(
+        env.refund_player(&m, &m.player_a.pubkey()).await,
+        CustomError::NothingToRefund,
+    );
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn resolution_not_expired() {
+    let mut env = TestEnv::new().await;
+
+    // partida sem os dois depósitos: só expira depois do `fund_by`
+    let m = env.create_match().await;
+    let fund_by = env.get_match(&m.key).await.fund_by;
+    env.warp_to(fund_by - 1).await;
+    assert_custom_error(env.expire(&m).await, CustomError::ResolutionNotExpired);
+
+    // partida financiada: só expira depois do `resolve_by`
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    let resolve_by = env.get_match(&m.key).await.resolve_by;
+    env.warp_to(resolve_by - 1).await;
+    assert_custom_error(env.expire(&m).await, CustomError::ResolutionNotExpired);
+}
+
+// ---------------------------------------------------------------------------
+// Pool sem apostas no vencedor
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn pool_not_unmatched() {
+    let mut env = TestEnv::new().await;
+    let m = env
+        .create_match_with(|args| args.unmatched_pool_policy = UnmatchedPoolPolicy::Treasury)
+        .await;
+    env.fund(&m).await;
+    env.bettor(&m, Side::PlayerA, STAKE).await;
+    env.resolve(&m, Side::PlayerA).await;
+
+    let treasury = env.treasury.pubkey();
+    assert_custom_error(
+        env.claim_unmatched_pool(&m, &treasury).await,
+        CustomError::PoolNotUnmatched,
+    );
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn wrong_unmatched_pool_policy() {
+    let mut env = TestEnv::new().await;
+    let m = env
+        .create_match_with(|args| args.unmatched_pool_policy = UnmatchedPoolPolicy::RefundBettors)
+        .await;
+    env.fund(&m).await;
+    env.bettor(&m, Side::PlayerB, STAKE).await;
+    env.resolve(&m, Side::PlayerA).await;
+
+    let treasury = env.treasury.pubkey();
+    assert_custom_error(
+        env.claim_unmatched_pool(&m, &treasury).await,
+        CustomError::WrongUnmatchedPoolPolicy,
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Prazo de saque
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn claim_window_expired() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    let winner = env.bettor(&m, Side::PlayerA, STAKE).await;
+    env.resolve(&m, Side::PlayerA).await;
+
+    let claim_deadline = env.get_match(&m.key).await.claim_deadline;
+    env.warp_to(claim_deadline).await;
+    assert_custom_error(
+        env.withdraw_winner_stake(&m, &m.player_a).await,
+        CustomError::ClaimWindowExpired,
+    );
+    assert_custom_error(
+        env.claim_bet_payout(&m, &winner).await,
+        CustomError::ClaimWindowExpired,
+    );
+
+    // pool sem apostas no vencedor: nem o saque nem o reembolso
+    let treasury = env.treasury.pubkey();
+    for policy in [
+        UnmatchedPoolPolicy::Treasury,
+        UnmatchedPoolPolicy::RefundBettors,
+    ] {
+        let m = env
+            .create_match_with(|args| args.unmatched_pool_policy = policy)
+            .await;
+        env.fund(&m).await;
+        let bettor = env.bettor(&m, Side::PlayerB, STAKE).await;
+        env.resolve(&m, Side::PlayerA).await;
+        env.warp_by(CLAIM_WINDOW).await;
+
+        let result = match policy {
+            UnmatchedPoolPolicy::RefundBettors => env.refund_bet(&m, &bettor.pubkey()).await,
+            _ => env.claim_unmatched_pool(&m, &treasury).await,
+        };
+        assert_custom_error(result, CustomError::ClaimWindowExpired);
+    }
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn claim_window_open() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    env.resolve(&m, Side::PlayerA).await;
+
+    let claim_deadline = env.get_match(&m.key).await.claim_deadline;
+    env.warp_to(claim_deadline - 1).await;
+    let arbiter = env.arbiter.insecure_clone();
+    assert_custom_error(
+        env.sweep_unclaimed(&m, &arbiter).await,
+        CustomError::ClaimWindowOpen,
+    );
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn not_match_authority() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    env.resolve(&m, Side::PlayerA).await;
+    env.warp_by(CLAIM_WINDOW).await;
+
+    // nem o jogador vencedor pode varrer o que não foi sacado
+    let winner = m.player_a.insecure_clone();
+    assert_custom_error(
+        env.sweep_unclaimed(&m, &winner).await,
+        CustomError::NotMatchAuthority,
+    );
+
+    // nem suspender a partida
+    assert_custom_error(
+        env.set_match_suspended(&m, &winner, true, true).await,
+        CustomError::NotMatchAuthority,
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Pausa do protocolo e suspensão de partidas
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn protocol_paused() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.join(&m, &m.player_a).await.unwrap();
+    let bettor = env.bettor(&m, Side::PlayerA, STAKE).await;
+
+    env.set_paused(true, false).await;
+    assert_custom_error(
+        env.try_create_match(None, |_| {}).await.map(|_| ()),
+        CustomError::ProtocolPaused,
+    );
+    assert_custom_error(env.join(&m, &m.player_b).await, CustomError::ProtocolPaused);
+    assert_custom_error(
+        env.bet(&m, &bettor, Side::PlayerA, STAKE).await,
+        CustomError::ProtocolPaused,
+    );
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn match_suspended() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.join(&m, &m.player_a).await.unwrap();
+    let bettor = env.bettor(&m, Side::PlayerA, STAKE).await;
+
+    let arbiter = env.arbiter.insecure_clone();
+    env.set_match_suspended(&m, &arbiter, true, false)
+        .await
+        .unwrap();
+    assert_custom_error(env.join(&m, &m.player_b).await, CustomError::MatchSuspended);
+    assert_custom_error(
+        env.bet(&m, &bettor, Side::PlayerA, STAKE).await,
+        CustomError::MatchSuspended,
+    );
+
+    // as outras partidas continuam abertas
+    env.create_match().await;
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn withdrawals_paused() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    let winner = env.bettor(&m, Side::PlayerA, STAKE).await;
+    env.resolve(&m, Side::PlayerA).await;
+
+    // só pausar depósitos não segura os saques
+    env.set_paused(true, false).await;
+    env.withdraw_winner_stake(&m, &m.player_a).await.unwrap();
+
+    env.set_paused(true, true).await;
+    assert_custom_error(
+        env.claim_bet_payout(&m, &winner).await,
+        CustomError::WithdrawalsPaused,
+    );
+
+    // reembolsos também
+    env.set_paused(false, false).await;
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    env.cancel(&m).await.unwrap();
+    env.set_paused(false, true).await;
+    assert_custom_error(
+        env.refund_player(&m, &m.player_a.pubkey()).await,
+        CustomError::WithdrawalsPaused,
+    );
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn match_withdrawals_suspended() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    let bettor = env.bettor(&m, Side::PlayerA, STAKE).await;
+    env.cancel(&m).await.unwrap();
+
+    // o admin também pode suspender a partida
+    let admin = env.admin.insecure_clone();
+    env.set_match_suspended(&m, &admin, false, true)
+        .await
+        .unwrap();
+    assert_custom_error(
+        env.refund_bet(&m, &bettor.pubkey()).await,
+        CustomError::MatchWithdrawalsSuspended,
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Fechamento das contas
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn participant_not_settled() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    let winner = env.bettor(&m, Side::PlayerA, STAKE).await;
+
+    // partida em aberto
+    assert_custom_error(
+        env.close_participant(&m, &winner.pubkey()).await,
+        CustomError::ParticipantNotSettled,
+    );
+
+    // vencedor que ainda não sacou
+    env.resolve(&m, Side::PlayerA).await;
+    assert_custom_error(
+        env.close_participant(&m, &winner.pubkey()).await,
+        CustomError::ParticipantNotSettled,
+    );
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn match_not_settled() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    assert_custom_error(env.close_match(&m).await, CustomError::MatchNotSettled);
+
+    // stakes ainda não sacados
+    env.resolve(&m, Side::PlayerA).await;
+    assert_custom_error(env.close_match(&m).await, CustomError::MatchNotSettled);
+
+    // pool de `WinningPlayer` ainda não sacado
+    let m = env
+        .create_match_with(|args| args.unmatched_pool_policy = UnmatchedPoolPolicy::WinningPlayer)
+        .await;
+    env.fund(&m).await;
+    let loser = env.bettor(&m, Side::PlayerB, STAKE).await;
+    env.resolve(&m, Side::PlayerA).await;
+    env.withdraw_winner_stake(&m, &m.player_a).await.unwrap();
+    env.close_participant(&m, &loser.pubkey()).await.unwrap();
+    assert_custom_error(env.close_match(&m).await, CustomError::MatchNotSettled);
+
+    // sobra de arredondamento ainda não varrida
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    let winner = env.bettor(&m, Side::PlayerA, STAKE).await;
+    env.resolve(&m, Side::PlayerA).await;
+    env.withdraw_winner_stake(&m, &m.player_a).await.unwrap();
+    env.claim_bet_payout(&m, &winner).await.unwrap();
+    env.close_participant(&m, &winner.pubkey()).await.unwrap();
+    assert_custom_error(env.close_match(&m).await, CustomError::MatchNotSettled);
+}
+
+// ---------------------------------------------------------------------------
+// Contas de token
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn missing_token_accounts() {
+    let mut env = TestEnv::new().await;
+    let spl = env.spl_token_mint().await;
+    let m = env.try_create_match(Some(spl), |_| {}).await.unwrap();
+
+    // partida SPL sem o mint/vault/conta de token
+    let result = env
+        .send(
+            &[instructions::join_as_player(
+                m.key,
+                m.player_a.pubkey(),
+                None,
+            )],
+            &[&m.player_a],
+        )
+        .await;
+    assert_custom_error(result, CustomError::MissingTokenAccounts);
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn invalid_token_account() {
+    let mut env = TestEnv::new().await;
+    let spl = env.spl_token_mint().await;
+    let m = env.try_create_match(Some(spl), |_| {}).await.unwrap();
+    let bettor = env.wallet(Some(spl)).await;
+    let other = env.wallet(Some(spl)).await;
+
+    // conta de token de outra wallet
+    let mut ix = instructions::place_bet(m.key, bettor.pubkey(), Side::PlayerA, STAKE, Some(spl));
+    replace_account(
+        &mut ix,
+        spl.token_account(&bettor.pubkey()),
+        spl.token_account(&other.pubkey()),
+    );
+    let result = env.send(&[ix], &[&bettor]).await;
+    assert_custom_error(result, CustomError::InvalidTokenAccount);
+
+    // mint diferente do da partida
+    let other_mint = env.spl_token_mint().await;
+    env.create_token_account(other_mint, &bettor.pubkey()).await;
+    env.mint_to(other_mint, &bettor.pubkey(), STAKE).await;
+    let result = env
+        .send(
+            &[instructions::place_bet(
+                m.key,
+                bettor.pubkey(),
+                Side::PlayerA,
+                STAKE,
+                Some(other_mint),
+            )],
+            &[&bettor],
+        )
+        .await;
+    assert_custom_error(result, CustomError::InvalidTokenAccount);
+}
+
+/// Troca uma conta da instrução por outra (mantendo as flags)
+fn replace_account(ix: &mut Instruction, from: Pubkey, to: Pubkey) {
+    let meta = ix
+        .accounts
+        .iter_mut()
+        .find(|meta| meta.pubkey == from)
+        .expect("account not in instruction");
+    meta.pubkey = to;
+}
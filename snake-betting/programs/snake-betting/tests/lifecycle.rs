@@ -0,0 +1,639 @@
+//! Fluxos completos das partidas em SOL: config, criação, depósitos, apostas,
+//! resolução (vencedor, empate, W.O.), cancelamento, expiração, reembolsos e
+//! fechamento das contas.
+
+mod common;
+
+use common::*;
+use snake_betting_client::{
+    instructions, pda, CustomError, MatchStatus, Side, UnmatchedPoolPolicy,
+};
+use solana_sdk::signature::{Keypair, Signer};
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn admin_updates_config_and_hands_over_authority() {
+    let mut env = TestEnv::new().await;
+
+    let config = env.config().await;
+    assert_eq!(config.admin, env.admin.pubkey());
+    assert_eq!(config.treasury, env.treasury.pubkey());
+    assert_eq!(config.protocol_fee_bps, 0);
+    assert_eq!(config.claim_window, CLAIM_WINDOW);
+
+    let new_treasury = Keypair::new().pubkey();
+    env.update_config(|args| {
+        args.treasury = new_treasury;
+        args.protocol_fee_bps = 150;
+        args.max_match_duration = 3_600;
+        args.claim_window = 600;
+    })
+    .await;
+
+    let config = env.config().await;
+    assert_eq!(config.treasury, new_treasury);
+    assert_eq!(config.protocol_fee_bps, 150);
+    assert_eq!(config.max_match_duration, 3_600);
+    assert_eq!(config.claim_window, 600);
+
+    let admin = env.admin.insecure_clone();
+    let new_admin = Keypair::new();
+    env.send(
+        &[instructions::set_admin(admin.pubkey(), new_admin.pubkey())],
+        &[&admin],
+    )
+    .await
+    .unwrap();
+    assert_eq!(env.config().await.admin, new_admin.pubkey());
+
+    // o admin novo passa a poder alterar a config
+    let args = default_config(new_treasury);
+    env.send(
+        &[instructions::update_config(new_admin.pubkey(), args)],
+        &[&new_admin],
+    )
+    .await
+    .unwrap();
+    assert_eq!(env.config().await.protocol_fee_bps, 0);
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn match_ids_follow_the_arbiter_counter() {
+    let mut env = TestEnv::new().await;
+
+    let first = env.create_match().await;
+    let second = env.create_match().await;
+
+    let arbiter = env.arbiter.pubkey();
+    assert_eq!(first.key, pda::match_address(&arbiter, 0));
+    assert_eq!(second.key, pda::match_address(&arbiter, 1));
+
+    let m = env.get_match(&second.key).await;
+    assert_eq!(m.id, 1);
+    assert_eq!(m.arbiter, arbiter);
+    assert_eq!(m.player_a, second.player_a.pubkey());
+    assert_eq!(m.player_b, second.player_b.pubkey());
+    assert_eq!(m.stake_lamports, STAKE);
+    assert_eq!(m.status, MatchStatus::Created);
+    assert_eq!(m.mint, None);
+    assert_eq!(m.escrowed, 0);
+
+    // o vault de lamports nasce só com o rent mínimo
+    let rent_floor = env.rent_floor().await;
+    assert_eq!(
+        env.balance(&pda::vault_address(&second.key)).await,
+        rent_floor
+    );
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn winner_withdraws_stakes_and_bettors_split_the_pool() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+
+    env.join(&m, &m.player_a).await.unwrap();
+    assert_eq!(env.get_match(&m.key).await.status, MatchStatus::Created);
+    env.join(&m, &m.player_b).await.unwrap();
+
+    let state = env.get_match(&m.key).await;
+    assert_eq!(state.status, MatchStatus::Funded);
+    assert_eq!(state.stake_pot, 2 * STAKE);
+    assert_eq!(state.player_a_stake, STAKE);
+    assert_eq!(state.player_b_stake, STAKE);
+
+    let a1 = env.bettor(&m, Side::PlayerA, 2 * STAKE).await;
+    let a2 = env.bettor(&m, Side::PlayerA, STAKE).await;
+    let b1 = env.bettor(&m, Side::PlayerB, 3 * STAKE).await;
+
+    let state = env.get_match(&m.key).await;
+    assert_eq!(state.total_side_a, 3 * STAKE);
+    assert_eq!(state.total_side_b, 3 * STAKE);
+    assert_eq!(state.open_participants, 3);
+    assert_eq!(state.participants_side_a, 2);
+    assert_eq!(state.participants_side_b, 1);
+    assert_eq!(state.escrowed, 8 * STAKE);
+
+    env.start(&m).await.unwrap();
+    let state = env.get_match(&m.key).await;
+    assert_eq!(state.status, MatchStatus::InProgress);
+    assert_eq!(state.started_at, env.now().await);
+
+    env.warp_to(state.earliest_resolution).await;
+    env.declare_winner(&m, Side::PlayerA).await.unwrap();
+
+    let state = env.get_match(&m.key).await;
+    assert_eq!(state.status, MatchStatus::Resolved);
+    assert_eq!(state.winner, Some(Side::PlayerA));
+    assert_eq!(state.payout_pool, 6 * STAKE);
+
+    let before = env.balance(&m.player_a.pubkey()).await;
+    env.withdraw_winner_stake(&m, &m.player_a).await.unwrap();
+    assert_eq!(env.balance(&m.player_a.pubkey()).await, before + 2 * STAKE);
+
+    // payout = aposta * pool / total do lado vencedor
+    for (bettor, payout) in [(&a1, 4 * STAKE), (&a2, 2 * STAKE)] {
+        let before = env.balance(&bettor.pubkey()).await;
+        env.claim_bet_payout(&m, bettor).await.unwrap();
+        assert_eq!(env.balance(&bettor.pubkey()).await, before + payout);
+        assert!(env.participant(&m, &bettor.pubkey()).await.claimed);
+    }
+
+    // quem apostou no perdedor não recebe nada, mas pode fechar a participação
+    for bettor in [&a1, &a2, &b1] {
+        env.close_participant(&m, &bettor.pubkey()).await.unwrap();
+        assert!(
+            !env.exists(&pda::participant_address(&m.key, &bettor.pubkey()))
+                .await
+        );
+    }
+
+    let state = env.get_match(&m.key).await;
+    assert_eq!(state.escrowed, 0);
+    assert_eq!(state.open_participants, 0);
+    assert_eq!(state.payouts_claimed, 2);
+
+    // sem sobra de arredondamento, a varredura só marca a partida
+    let treasury = env.treasury.pubkey();
+    env.sweep_dust(&m, &treasury).await.unwrap();
+    assert!(env.get_match(&m.key).await.dust_swept);
+
+    close_and_check_rent(&mut env, &m).await;
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn rounding_dust_goes_to_the_winning_player() {
+    let mut env = TestEnv::new().await;
+    let m = env
+        .create_match_with(|args| args.unmatched_pool_policy = UnmatchedPoolPolicy::WinningPlayer)
+        .await;
+    env.fund(&m).await;
+
+    // 3 apostas iguais dividindo um pool de 4 SOL + 1 lamport
+    let winners = [
+        env.bettor(&m, Side::PlayerA, STAKE).await,
+        env.bettor(&m, Side::PlayerA, STAKE).await,
+        env.bettor(&m, Side::PlayerA, STAKE).await,
+    ];
+    let loser = env.bettor(&m, Side::PlayerB, STAKE + 1).await;
+    env.resolve(&m, Side::PlayerA).await;
+    env.withdraw_winner_stake(&m, &m.player_a).await.unwrap();
+
+    let payout_pool = env.get_match(&m.key).await.payout_pool;
+    let payout = STAKE * payout_pool / (3 * STAKE);
+    for bettor in &winners {
+        env.claim_bet_payout(&m, bettor).await.unwrap();
+    }
+
+    let state = env.get_match(&m.key).await;
+    assert_eq!(state.participants_side_a, 3);
+    assert_eq!(state.payouts_claimed, 3);
+    let dust = payout_pool - 3 * payout;
+    assert!(dust > 0);
+    assert_eq!(state.escrowed, dust);
+
+    let winner = m.player_a.pubkey();
+    let before = env.balance(&winner).await;
+    env.sweep_dust(&m, &winner).await.unwrap();
+    assert_eq!(env.balance(&winner).await, before + dust);
+    assert_eq!(env.get_match(&m.key).await.escrowed, 0);
+
+    for bettor in winners.iter().chain([&loser]) {
+        env.close_participant(&m, &bettor.pubkey()).await.unwrap();
+    }
+    close_and_check_rent(&mut env, &m).await;
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn unclaimed_winnings_go_to_the_treasury_after_the_claim_deadline() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    let claimer = env.bettor(&m, Side::PlayerA, STAKE).await;
+    let absent = env.bettor(&m, Side::PlayerA, STAKE).await;
+    let loser = env.bettor(&m, Side::PlayerB, STAKE).await;
+
+    // o prazo de saque da Config fica congelado na partida e começa na resolução
+    env.update_config(|args| args.claim_window = 10).await;
+    env.resolve(&m, Side::PlayerA).await;
+    let state = env.get_match(&m.key).await;
+    assert_eq!(state.claim_window, CLAIM_WINDOW);
+    assert_eq!(state.claim_deadline, env.now().await + CLAIM_WINDOW);
+
+    // só um dos vencedores saca; o jogador vencedor não saca o stake
+    env.claim_bet_payout(&m, &claimer).await.unwrap();
+    let unclaimed = env.get_match(&m.key).await.escrowed;
+    assert_eq!(unclaimed, 2 * STAKE + 3 * STAKE / 2);
+
+    // depois do prazo o admin varre tudo pra tesouraria
+    env.warp_to(state.claim_deadline).await;
+    let treasury = env.treasury.pubkey();
+    let before = env.balance(&treasury).await;
+    let admin = env.admin.insecure_clone();
+    env.sweep_unclaimed(&m, &admin).await.unwrap();
+    assert_eq!(env.balance(&treasury).await, before + unclaimed);
+
+    let state = env.get_match(&m.key).await;
+    assert!(state.unclaimed_swept);
+    assert_eq!(state.escrowed, 0);
+    assert_eq!(state.stake_pot, 0);
+
+    // quem não sacou perde o prêmio, mas recebe o rent da participação
+    for bettor in [&claimer, &absent, &loser] {
+        env.close_participant(&m, &bettor.pubkey()).await.unwrap();
+    }
+    close_and_check_rent(&mut env, &m).await;
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn paused_match_resumes_where_it_stopped() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    let bettor = env.bettor(&m, Side::PlayerA, STAKE).await;
+
+    // o árbitro suspende tudo na partida, o admin pausa o protocolo
+    let arbiter = env.arbiter.insecure_clone();
+    env.set_match_suspended(&m, &arbiter, true, true)
+        .await
+        .unwrap();
+    env.set_paused(true, true).await;
+    let state = env.get_match(&m.key).await;
+    assert!(state.suspended && state.withdrawals_suspended);
+    let config = env.config().await;
+    assert!(config.paused && config.withdrawals_paused);
+
+    // a partida ainda pode ser resolvida durante a pausa
+    env.resolve(&m, Side::PlayerA).await;
+
+    env.set_paused(false, false).await;
+    env.set_match_suspended(&m, &arbiter, false, false)
+        .await
+        .unwrap();
+    env.withdraw_winner_stake(&m, &m.player_a).await.unwrap();
+    let before = env.balance(&bettor.pubkey()).await;
+    env.claim_bet_payout(&m, &bettor).await.unwrap();
+    assert_eq!(env.balance(&bettor.pubkey()).await, before + STAKE);
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn suspended_withdrawals_stop_the_claim_deadline() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    let bettor = env.bettor(&m, Side::PlayerA, STAKE).await;
+    env.resolve(&m, Side::PlayerA).await;
+    let deadline = env.get_match(&m.key).await.claim_deadline;
+
+    // saques suspensos até depois do prazo original
+    let arbiter = env.arbiter.insecure_clone();
+    env.set_match_suspended(&m, &arbiter, false, true)
+        .await
+        .unwrap();
+    let suspended_at = env.now().await;
+    env.warp_to(deadline + 100).await;
+    env.set_match_suspended(&m, &arbiter, false, false)
+        .await
+        .unwrap();
+
+    // o tempo suspenso não conta: o prazo anda junto
+    let extended = env.get_match(&m.key).await.claim_deadline;
+    assert_eq!(extended, deadline + (deadline + 100 - suspended_at));
+    let admin = env.admin.insecure_clone();
+    let result = env.sweep_unclaimed(&m, &admin).await;
+    assert_custom_error(result, CustomError::ClaimWindowOpen);
+    env.claim_bet_payout(&m, &bettor).await.unwrap();
+
+    env.warp_to(extended).await;
+    env.sweep_unclaimed(&m, &admin).await.unwrap();
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn paused_withdrawals_stop_the_claim_deadline() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    let bettor = env.bettor(&m, Side::PlayerA, STAKE).await;
+    env.resolve(&m, Side::PlayerA).await;
+    let deadline = env.get_match(&m.key).await.claim_deadline;
+
+    // o protocolo pausa os saques até depois do prazo original
+    env.set_paused(false, true).await;
+    let paused_at = env.now().await;
+    env.warp_to(deadline + 100).await;
+    env.set_paused(false, false).await;
+
+    let admin = env.admin.insecure_clone();
+    let result = env.sweep_unclaimed(&m, &admin).await;
+    assert_custom_error(result, CustomError::ClaimWindowOpen);
+    env.claim_bet_payout(&m, &bettor).await.unwrap();
+
+    env.warp_to(deadline + (deadline + 100 - paused_at)).await;
+    env.sweep_unclaimed(&m, &admin).await.unwrap();
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn repeated_bets_accumulate_on_the_same_side() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.fund(&m).await;
+
+    let bettor = env.bettor(&m, Side::PlayerB, STAKE).await;
+    env.bet(&m, &bettor, Side::PlayerB, 2 * STAKE)
+        .await
+        .unwrap();
+
+    let p = env.participant(&m, &bettor.pubkey()).await;
+    assert_eq!(p.side, Side::PlayerB);
+    assert_eq!(p.amount, 3 * STAKE);
+
+    let state = env.get_match(&m.key).await;
+    assert_eq!(state.total_side_b, 3 * STAKE);
+    assert_eq!(state.open_participants, 1);
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn fees_go_to_treasury_and_arbiter_on_resolution() {
+    let mut env = TestEnv::new().await;
+    env.update_config(|args| args.protocol_fee_bps = 200).await;
+
+    let m = env
+        .create_match_with(|args| args.arbiter_fee_bps = 300)
+        .await;
+    env.fund(&m).await;
+    let winner_bettor = env.bettor(&m, Side::PlayerA, STAKE).await;
+    env.bettor(&m, Side::PlayerB, STAKE).await;
+
+    let treasury = env.treasury.pubkey();
+    let arbiter = env.arbiter.pubkey();
+    let treasury_before = env.balance(&treasury).await;
+    let arbiter_before = env.balance(&arbiter).await;
+
+    env.resolve(&m, Side::PlayerA).await;
+
+    // 2% e 3% sobre 2 SOL de stakes + 2 SOL de apostas
+    let state = env.get_match(&m.key).await;
+    assert_eq!(state.protocol_fee, 4 * STAKE / 50);
+    assert_eq!(state.arbiter_fee, 4 * STAKE * 3 / 100);
+    assert_eq!(
+        env.balance(&treasury).await,
+        treasury_before + state.protocol_fee
+    );
+    assert_eq!(
+        env.balance(&arbiter).await,
+        arbiter_before + state.arbiter_fee
+    );
+
+    let net = 2 * STAKE * 95 / 100;
+    assert_eq!(state.stake_pot, net);
+    assert_eq!(state.payout_pool, net);
+
+    let before = env.balance(&m.player_a.pubkey()).await;
+    env.withdraw_winner_stake(&m, &m.player_a).await.unwrap();
+    assert_eq!(env.balance(&m.player_a.pubkey()).await, before + net);
+
+    let before = env.balance(&winner_bettor.pubkey()).await;
+    env.claim_bet_payout(&m, &winner_bettor).await.unwrap();
+    assert_eq!(env.balance(&winner_bettor.pubkey()).await, before + net);
+
+    assert_eq!(env.get_match(&m.key).await.escrowed, 0);
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn draw_refunds_players_and_bettors() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    let a = env.bettor(&m, Side::PlayerA, STAKE).await;
+    let b = env.bettor(&m, Side::PlayerB, 2 * STAKE).await;
+
+    env.start_and_wait(&m).await;
+    env.declare_draw(&m).await.unwrap();
+    assert_eq!(env.get_match(&m.key).await.status, MatchStatus::Draw);
+
+    refund_everyone(&mut env, &m, &[(&a, STAKE), (&b, 2 * STAKE)]).await;
+    close_and_check_rent(&mut env, &m).await;
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn cancelled_match_refunds_players_and_bettors() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    let a = env.bettor(&m, Side::PlayerA, STAKE).await;
+
+    // dá pra cancelar mesmo com o jogo em andamento
+    env.start(&m).await.unwrap();
+    env.cancel(&m).await.unwrap();
+    assert_eq!(env.get_match(&m.key).await.status, MatchStatus::Cancelled);
+
+    refund_everyone(&mut env, &m, &[(&a, STAKE)]).await;
+    close_and_check_rent(&mut env, &m).await;
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn arbiter_declares_walkover_for_the_only_depositor() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.join(&m, &m.player_a).await.unwrap();
+    let bettor = env.bettor(&m, Side::PlayerB, STAKE).await;
+
+    // o W.O. só vale depois do prazo de depósito
+    let fund_by = env.get_match(&m.key).await.fund_by;
+    env.warp_to(fund_by).await;
+    env.declare_walkover(&m).await.unwrap();
+
+    let state = env.get_match(&m.key).await;
+    assert_eq!(state.status, MatchStatus::Walkover);
+    assert_eq!(state.winner, Some(Side::PlayerA));
+
+    // o vencedor recebe só o próprio stake; as apostas voltam
+    let before = env.balance(&m.player_a.pubkey()).await;
+    env.withdraw_winner_stake(&m, &m.player_a).await.unwrap();
+    assert_eq!(env.balance(&m.player_a.pubkey()).await, before + STAKE);
+
+    let before = env.balance(&bettor.pubkey()).await;
+    env.refund_bet(&m, &bettor.pubkey()).await.unwrap();
+    assert_eq!(env.balance(&bettor.pubkey()).await, before + STAKE);
+
+    env.close_participant(&m, &bettor.pubkey()).await.unwrap();
+    close_and_check_rent(&mut env, &m).await;
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn player_claims_walkover_after_fund_by() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.join(&m, &m.player_b).await.unwrap();
+
+    let fund_by = env.get_match(&m.key).await.fund_by;
+    env.warp_to(fund_by).await;
+    env.send(
+        &[instructions::claim_walkover(m.key, m.player_b.pubkey())],
+        &[&m.player_b],
+    )
+    .await
+    .unwrap();
+
+    let state = env.get_match(&m.key).await;
+    assert_eq!(state.status, MatchStatus::Walkover);
+    assert_eq!(state.winner, Some(Side::PlayerB));
+
+    env.withdraw_winner_stake(&m, &m.player_b).await.unwrap();
+    close_and_check_rent(&mut env, &m).await;
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn unfunded_match_expires_after_fund_by() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.join(&m, &m.player_a).await.unwrap();
+    let bettor = env.bettor(&m, Side::PlayerA, STAKE).await;
+
+    let fund_by = env.get_match(&m.key).await.fund_by;
+    env.warp_to(fund_by).await;
+    env.expire(&m).await.unwrap();
+    assert_eq!(env.get_match(&m.key).await.status, MatchStatus::Cancelled);
+
+    let before = env.balance(&m.player_a.pubkey()).await;
+    env.refund_player(&m, &m.player_a.pubkey()).await.unwrap();
+    assert_eq!(env.balance(&m.player_a.pubkey()).await, before + STAKE);
+
+    env.refund_bet(&m, &bettor.pubkey()).await.unwrap();
+    env.close_participant(&m, &bettor.pubkey()).await.unwrap();
+    close_and_check_rent(&mut env, &m).await;
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn unresolved_match_expires_after_resolve_by() {
+    let mut env = TestEnv::new().await;
+    let m = env.create_match().await;
+    env.fund(&m).await;
+    let bettor = env.bettor(&m, Side::PlayerB, STAKE).await;
+    env.start(&m).await.unwrap();
+
+    let resolve_by = env.get_match(&m.key).await.resolve_by;
+    env.warp_to(resolve_by).await;
+    env.expire(&m).await.unwrap();
+    assert_eq!(env.get_match(&m.key).await.status, MatchStatus::Cancelled);
+
+    refund_everyone(&mut env, &m, &[(&bettor, STAKE)]).await;
+    close_and_check_rent(&mut env, &m).await;
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn unmatched_pool_is_refunded_to_bettors() {
+    let mut env = TestEnv::new().await;
+    let m = env
+        .create_match_with(|args| args.unmatched_pool_policy = UnmatchedPoolPolicy::RefundBettors)
+        .await;
+    env.fund(&m).await;
+    let bettor = env.bettor(&m, Side::PlayerB, STAKE).await;
+    env.resolve(&m, Side::PlayerA).await;
+
+    let before = env.balance(&bettor.pubkey()).await;
+    env.refund_bet(&m, &bettor.pubkey()).await.unwrap();
+    assert_eq!(env.balance(&bettor.pubkey()).await, before + STAKE);
+
+    env.close_participant(&m, &bettor.pubkey()).await.unwrap();
+    env.withdraw_winner_stake(&m, &m.player_a).await.unwrap();
+    close_and_check_rent(&mut env, &m).await;
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn unmatched_pool_goes_to_the_winning_player() {
+    let mut env = TestEnv::new().await;
+    let m = env
+        .create_match_with(|args| args.unmatched_pool_policy = UnmatchedPoolPolicy::WinningPlayer)
+        .await;
+    env.fund(&m).await;
+    let bettor = env.bettor(&m, Side::PlayerA, 3 * STAKE).await;
+    env.resolve(&m, Side::PlayerB).await;
+
+    env.withdraw_winner_stake(&m, &m.player_b).await.unwrap();
+    env.close_participant(&m, &bettor.pubkey()).await.unwrap();
+
+    let winner = m.player_b.pubkey();
+    let before = env.balance(&winner).await;
+    env.claim_unmatched_pool(&m, &winner).await.unwrap();
+    assert_eq!(env.balance(&winner).await, before + 3 * STAKE);
+    assert!(env.get_match(&m.key).await.unmatched_pool_claimed);
+
+    close_and_check_rent(&mut env, &m).await;
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn unmatched_pool_goes_to_the_treasury() {
+    let mut env = TestEnv::new().await;
+    let m = env
+        .create_match_with(|args| args.unmatched_pool_policy = UnmatchedPoolPolicy::Treasury)
+        .await;
+    env.fund(&m).await;
+    let bettor = env.bettor(&m, Side::PlayerB, 2 * STAKE).await;
+    env.resolve(&m, Side::PlayerA).await;
+
+    env.withdraw_winner_stake(&m, &m.player_a).await.unwrap();
+    env.close_participant(&m, &bettor.pubkey()).await.unwrap();
+
+    // o pool ainda é da tesouraria: a Match não pode fechar antes do saque
+    assert_custom_error(env.close_match(&m).await, CustomError::MatchNotSettled);
+
+    let treasury = env.treasury.pubkey();
+    let before = env.balance(&treasury).await;
+    env.claim_unmatched_pool(&m, &treasury).await.unwrap();
+    assert_eq!(env.balance(&treasury).await, before + 2 * STAKE);
+
+    close_and_check_rent(&mut env, &m).await;
+}
+
+/// Reembolsa os dois jogadores e os apostadores (com o valor esperado de cada
+/// um) e fecha as participações.
+async fn refund_everyone(env: &mut TestEnv, m: &TestMatch, bettors: &[(&Keypair, u64)]) {
+    for player in [&m.player_a, &m.player_b] {
+        let before = env.balance(&player.pubkey()).await;
+        env.refund_player(m, &player.pubkey()).await.unwrap();
+        assert_eq!(env.balance(&player.pubkey()).await, before + STAKE);
+    }
+
+    for (bettor, amount) in bettors {
+        let before = env.balance(&bettor.pubkey()).await;
+        env.refund_bet(m, &bettor.pubkey()).await.unwrap();
+        assert_eq!(env.balance(&bettor.pubkey()).await, before + amount);
+
+        env.close_participant(m, &bettor.pubkey()).await.unwrap();
+    }
+
+    assert_eq!(env.get_match(&m.key).await.escrowed, 0);
+}
+
+/// Fecha a partida e confere que o rent da Match e do vault voltou pro árbitro.
+async fn close_and_check_rent(env: &mut TestEnv, m: &TestMatch) {
+    let vault = pda::vault_address(&m.key);
+    let arbiter = env.arbiter.pubkey();
+
+    let rent = env.balance(&m.key).await + env.balance(&vault).await;
+    let before = env.balance(&arbiter).await;
+
+    env.close_match(m).await.unwrap();
+
+    assert!(!env.exists(&m.key).await);
+    assert!(!env.exists(&vault).await);
+    assert_eq!(env.balance(&arbiter).await, before + rent);
+}
@@ -0,0 +1,127 @@
+//! Propriedades da matemática de liquidação (`settlement`) com distribuições
+//! de apostas aleatórias. Não precisa do programa compilado.
+
+use anchor_lang::error::Error;
+use proptest::prelude::*;
+use snake_betting::settlement::{bet_payout, split_fees};
+use snake_betting::{CustomError, BPS_DENOMINATOR, MAX_FEE_BPS};
+
+/// Maior número de apostadores por lado nos testes
+const MAX_BETTORS: usize = 64;
+
+/// Maior aposta individual: com `MAX_BETTORS` de cada lado, o total das
+/// apostas ainda cabe em u64 (como `total_side_a + total_side_b` on-chain)
+const MAX_BET: u64 = u64::MAX / (2 * MAX_BETTORS as u64);
+
+/// Apostas de um lado: valores pequenos (onde o arredondamento aparece mais)
+/// misturados com valores perto do limite
+fn bets() -> impl Strategy<Value = Vec<u64>> {
+    prop::collection::vec(prop_oneof![1..=1_000u64, 1..=MAX_BET], 1..=MAX_BETTORS)
+}
+
+/// Taxa do protocolo e do árbitro, com a soma dentro do teto absoluto
+fn fees() -> impl Strategy<Value = (u16, u16)> {
+    (0..=MAX_FEE_BPS).prop_flat_map(|protocol| (Just(protocol), 0..=MAX_FEE_BPS - protocol))
+}
+
+proptest! {
+    #[test]
+    fn payouts_never_exceed_the_pool_and_dust_is_bounded(
+        winners in bets(),
+        losers in bets(),
+        stake_pot in any::<u64>(),
+        (protocol_fee_bps, arbiter_fee_bps) in fees(),
+    ) {
+        let winner_bets_total: u64 = winners.iter().sum();
+        let pool_bets = winner_bets_total + losers.iter().sum::<u64>();
+
+        let split = split_fees(stake_pot, pool_bets, true, protocol_fee_bps, arbiter_fee_bps)
+            .unwrap();
+
+        let mut paid: u64 = 0;
+        for &amount in &winners {
+            let payout = bet_payout(amount, split.payout_pool, winner_bets_total).unwrap();
+            paid = paid.checked_add(payout).unwrap();
+        }
+
+        // nunca paga mais do que o pool, e cada prêmio perde menos de 1 unidade
+        // no arredondamento
+        prop_assert!(paid <= split.payout_pool);
+        let dust = split.payout_pool - paid;
+        prop_assert!(dust < winners.len() as u64);
+        if winners.len() == 1 {
+            prop_assert_eq!(dust, 0);
+        }
+    }
+
+    #[test]
+    fn winners_never_get_less_than_their_bet_without_fees(
+        winners in bets(),
+        losers in bets(),
+    ) {
+        let winner_bets_total: u64 = winners.iter().sum();
+        let pool_bets = winner_bets_total + losers.iter().sum::<u64>();
+        let split = split_fees(0, pool_bets, true, 0, 0).unwrap();
+
+        for &amount in &winners {
+            prop_assert!(bet_payout(amount, split.payout_pool, winner_bets_total).unwrap() >= amount);
+        }
+    }
+
+    #[test]
+    fn bet_payout_never_overflows(
+        (amount, winner_bets_total) in (1..=u64::MAX).prop_flat_map(|total| (1..=total, Just(total))),
+        payout_pool in any::<u64>(),
+    ) {
+        let payout = bet_payout(amount, payout_pool, winner_bets_total).unwrap();
+        prop_assert!(payout <= payout_pool);
+    }
+
+    #[test]
+    fn fee_split_conserves_value(
+        stake_pot in any::<u64>(),
+        pool_bets in any::<u64>(),
+        charge_pool in any::<bool>(),
+        (protocol_fee_bps, arbiter_fee_bps) in fees(),
+    ) {
+        let split = split_fees(stake_pot, pool_bets, charge_pool, protocol_fee_bps, arbiter_fee_bps)
+            .unwrap();
+
+        // nada é criado nem some: o que sai em taxas falta no pote/pool
+        let before = stake_pot as u128 + pool_bets as u128;
+        let after = split.stake_pot as u128
+            + split.payout_pool as u128
+            + split.protocol_fee as u128
+            + split.arbiter_fee as u128;
+        prop_assert_eq!(before, after);
+
+        // as taxas nunca passam da porcentagem configurada
+        let charged = stake_pot as u128 + if charge_pool { pool_bets as u128 } else { 0 };
+        let fee_bps = (protocol_fee_bps + arbiter_fee_bps) as u128;
+        prop_assert!(
+            (split.protocol_fee as u128 + split.arbiter_fee as u128)
+                <= charged * fee_bps / BPS_DENOMINATOR as u128
+        );
+
+        // pool reembolsável não paga taxa
+        if !charge_pool {
+            prop_assert_eq!(split.payout_pool, pool_bets);
+        }
+    }
+}
+
+#[test]
+fn bet_payout_without_bets_on_the_winner_side() {
+    assert_eq!(
+        bet_payout(0, 1_000, 0).unwrap_err(),
+        Error::from(CustomError::NoBetsOnWinnerSide)
+    );
+}
+
+#[test]
+fn bet_payout_rejects_bet_larger_than_the_side_total() {
+    assert_eq!(
+        bet_payout(2, 1_000, 1).unwrap_err(),
+        Error::from(CustomError::MathOverflow)
+    );
+}
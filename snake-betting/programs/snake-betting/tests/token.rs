@@ -0,0 +1,174 @@
+//! Partidas em token: SPL Token clássico, Token-2022 e Token-2022 com taxa de
+//! transferência (o escrow registra só o que realmente recebeu).
+
+mod common;
+
+use common::*;
+use snake_betting_client::{pda, MatchStatus, Side, SplMint};
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn spl_token_match_pays_out_in_tokens() {
+    let mut env = TestEnv::new().await;
+    let spl = env.spl_token_mint().await;
+    token_match_pays_out_in_tokens(&mut env, spl).await;
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn token_2022_match_pays_out_in_tokens() {
+    let mut env = TestEnv::new().await;
+    let spl = env.token_2022_mint(None).await;
+    token_match_pays_out_in_tokens(&mut env, spl).await;
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn token_2022_transfer_fee_is_charged_on_the_way_in_and_out() {
+    let mut env = TestEnv::new().await;
+    let spl = env.token_2022_mint(Some(100)).await;
+    let m = env.try_create_match(Some(spl), |_| {}).await.unwrap();
+
+    // o escrow registra o stake líquido da taxa de transferência
+    env.fund(&m).await;
+    let stake_fee = env.transfer_fee(spl, STAKE).await;
+    assert!(stake_fee > 0);
+    let state = env.get_match(&m.key).await;
+    assert_eq!(state.player_a_stake, STAKE - stake_fee);
+    assert_eq!(state.player_b_stake, STAKE - stake_fee);
+    assert_eq!(state.stake_pot, 2 * (STAKE - stake_fee));
+
+    // a aposta também conta só o que entrou no vault
+    let winner = env.bettor(&m, Side::PlayerA, STAKE).await;
+    let loser = env.bettor(&m, Side::PlayerB, STAKE).await;
+    let p = env.participant(&m, &winner.pubkey()).await;
+    assert_eq!(p.amount, STAKE - stake_fee);
+
+    let token_vault = pda::token_vault_address(&m.key);
+    let state = env.get_match(&m.key).await;
+    assert_eq!(state.escrowed, 4 * (STAKE - stake_fee));
+    assert_eq!(env.token_balance(&token_vault).await, state.escrowed);
+
+    env.resolve(&m, Side::PlayerA).await;
+
+    // no saque a taxa sai do que o destino recebe
+    let stake_pot = env.get_match(&m.key).await.stake_pot;
+    let before = env.tokens_of(spl, &m.player_a.pubkey()).await;
+    env.withdraw_winner_stake(&m, &m.player_a).await.unwrap();
+    let fee = env.transfer_fee(spl, stake_pot).await;
+    assert_eq!(
+        env.tokens_of(spl, &m.player_a.pubkey()).await,
+        before + stake_pot - fee
+    );
+
+    let payout_pool = env.get_match(&m.key).await.payout_pool;
+    let before = env.tokens_of(spl, &winner.pubkey()).await;
+    env.claim_bet_payout(&m, &winner).await.unwrap();
+    let fee = env.transfer_fee(spl, payout_pool).await;
+    assert_eq!(
+        env.tokens_of(spl, &winner.pubkey()).await,
+        before + payout_pool - fee
+    );
+
+    assert_eq!(env.get_match(&m.key).await.escrowed, 0);
+    assert_eq!(env.token_balance(&token_vault).await, 0);
+
+    // o vault ainda guarda as taxas retidas, que vão pro mint no fechamento
+    for bettor in [&winner, &loser] {
+        env.close_participant(&m, &bettor.pubkey()).await.unwrap();
+    }
+    env.sweep_dust(&m, &env.treasury.pubkey()).await.unwrap();
+    env.close_match(&m).await.unwrap();
+    assert!(!env.exists(&token_vault).await);
+    assert!(!env.exists(&m.key).await);
+}
+
+#[tokio::test]
+#[ignore = "precisa do programa compilado: anchor build"]
+async fn cancelled_token_match_refunds_in_tokens() {
+    let mut env = TestEnv::new().await;
+    let spl = env.token_2022_mint(None).await;
+    let m = env.try_create_match(Some(spl), |_| {}).await.unwrap();
+    env.fund(&m).await;
+    let bettor = env.bettor(&m, Side::PlayerB, 2 * STAKE).await;
+
+    env.cancel(&m).await.unwrap();
+    assert_eq!(env.get_match(&m.key).await.status, MatchStatus::Cancelled);
+
+    for player in [&m.player_a, &m.player_b] {
+        env.refund_player(&m, &player.pubkey()).await.unwrap();
+        assert_eq!(env.tokens_of(spl, &player.pubkey()).await, WALLET_TOKENS);
+    }
+    env.refund_bet(&m, &bettor.pubkey()).await.unwrap();
+    assert_eq!(env.tokens_of(spl, &bettor.pubkey()).await, WALLET_TOKENS);
+
+    env.close_participant(&m, &bettor.pubkey()).await.unwrap();
+    env.close_match(&m).await.unwrap();
+    assert!(!env.exists(&pda::token_vault_address(&m.key)).await);
+}
+
+/// Fluxo completo de uma partida em token, com taxas de protocolo e árbitro
+/// e uma sobra de arredondamento varrida pra tesouraria (`sweep_dust`).
+async fn token_match_pays_out_in_tokens(env: &mut TestEnv, spl: SplMint) {
+    env.update_config(|args| args.protocol_fee_bps = 100).await;
+    let m = env
+        .try_create_match(Some(spl), |args| args.arbiter_fee_bps = 200)
+        .await
+        .unwrap();
+    assert_eq!(env.get_match(&m.key).await.mint, Some(spl.mint));
+
+    env.fund(&m).await;
+    let a1 = env.bettor(&m, Side::PlayerA, STAKE).await;
+    let a2 = env.bettor(&m, Side::PlayerA, 2 * STAKE).await;
+    let b1 = env.bettor(&m, Side::PlayerB, STAKE + 1).await;
+
+    let token_vault = pda::token_vault_address(&m.key);
+    assert_eq!(env.token_balance(&token_vault).await, 6 * STAKE + 1);
+
+    env.resolve(&m, Side::PlayerA).await;
+
+    let state = env.get_match(&m.key).await;
+    let treasury = env.treasury.pubkey();
+    let arbiter = env.arbiter.pubkey();
+    assert_eq!(env.tokens_of(spl, &treasury).await, state.protocol_fee);
+    assert_eq!(env.tokens_of(spl, &arbiter).await, state.arbiter_fee);
+
+    env.withdraw_winner_stake(&m, &m.player_a).await.unwrap();
+    assert_eq!(
+        env.tokens_of(spl, &m.player_a.pubkey()).await,
+        WALLET_TOKENS - STAKE + state.stake_pot
+    );
+
+    // payout = aposta * pool / total do lado vencedor (arredondado pra baixo)
+    let mut paid = 0;
+    for (bettor, amount) in [(&a1, STAKE), (&a2, 2 * STAKE)] {
+        let payout = amount * state.payout_pool / state.total_side_a;
+        env.claim_bet_payout(&m, bettor).await.unwrap();
+        assert_eq!(
+            env.tokens_of(spl, &bettor.pubkey()).await,
+            WALLET_TOKENS - amount + payout
+        );
+        paid += payout;
+    }
+
+    for bettor in [&a1, &a2, &b1] {
+        env.close_participant(&m, &bettor.pubkey()).await.unwrap();
+    }
+
+    // a sobra do arredondamento fica no vault até ser varrida pra tesouraria
+    let dust = state.payout_pool - paid;
+    assert!(dust > 0);
+    assert_eq!(env.token_balance(&token_vault).await, dust);
+    env.sweep_dust(&m, &treasury).await.unwrap();
+    assert_eq!(env.token_balance(&token_vault).await, 0);
+    assert_eq!(
+        env.tokens_of(spl, &treasury).await,
+        state.protocol_fee + dust
+    );
+
+    env.close_match(&m).await.unwrap();
+    assert!(!env.exists(&token_vault).await);
+    assert!(!env.exists(&m.key).await);
+    assert_eq!(env.tokens_of(spl, &arbiter).await, state.arbiter_fee);
+}
@@ -0,0 +1,11 @@
+#!/usr/bin/env bash
+# Roda todos os testes Rust do workspace, inclusive os testes de integração do
+# programa, que ficam `#[ignore]` enquanto não existe o `.so` em target/deploy.
+#
+# Uso: ./scripts/test-rust.sh [argumentos extras pro binário de teste]
+set -euo pipefail
+
+cd "$(dirname "$0")/.."
+
+anchor build
+cargo test --workspace -- --include-ignored "$@"
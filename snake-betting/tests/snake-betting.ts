@@ -36,33 +36,103 @@ describe("snake-betting end-to-end", () => {
       await connection.confirmTransaction(sig, "confirmed");
     }
 
-    // 2) Derivar PDA da partida
+    // Autoridade dos eventos CPI (`emit_cpi!`): vai junto com o próprio programa
+    const [eventAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("__event_authority")],
+      program.programId
+    );
+    const eventAccounts = { eventAuthority, program: program.programId };
+
+    // 2) Configuração global do protocolo (criada uma vez só, logo após o deploy)
+    const [configPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      program.programId
+    );
+
+    if ((await connection.getAccountInfo(configPda)) === null) {
+      await program.methods
+        .initialize({
+          treasury: arbiter,
+          minStakeLamports: new anchor.BN(0),
+          minBetLamports: new anchor.BN(0),
+          maxMatchDuration: new anchor.BN(24 * 60 * 60),
+          protocolFeeBps: 0,
+          maxFeeBps: 1_000,
+          claimWindow: new anchor.BN(7 * 24 * 60 * 60),
+        })
+        .accounts({
+          admin: arbiter,
+          config: configPda,
+          systemProgram: SystemProgram.programId,
+          ...eventAccounts,
+        })
+        .rpc();
+    }
+
+    // Contador de partidas do árbitro (criado uma vez só)
+    const [counterPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("match_counter"), arbiter.toBuffer()],
+      program.programId
+    );
+
+    if ((await connection.getAccountInfo(counterPda)) === null) {
+      await program.methods
+        .initMatchCounter()
+        .accounts({
+          arbiter,
+          matchCounter: counterPda,
+          systemProgram: SystemProgram.programId,
+          ...eventAccounts,
+        })
+        .rpc();
+    }
+
+    const counter = await program.account.matchCounter.fetch(counterPda);
+
+    // Derivar PDA da partida (seeds: "match" + árbitro + id em u64 LE)
+    const id = counter.nextId;
     [matchPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("match"), arbiter.toBuffer()],
+      [Buffer.from("match"), arbiter.toBuffer(), id.toArrayLike(Buffer, "le", 8)],
       program.programId
     );
 
     console.log("Match PDA:", matchPda.toBase58());
 
+    // Vault de lamports da partida (seeds: "vault" + match): guarda o SOL do escrow
+    const [vaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), matchPda.toBuffer()],
+      program.programId
+    );
+
     // 3) Criar partida
-    const id = new anchor.BN(1);
     const stakeLamports = new anchor.BN(0.1 * LAMPORTS_PER_SOL); // 0.1 SOL de stake pra cada
     const now = Math.floor(Date.now() / 1000);
-    const deadline = new anchor.BN(now + 60); // 60s no futuro
+    const betsCloseAt = new anchor.BN(now + 60); // apostas abertas por 60s
+    const earliestResolution = betsCloseAt; // jogo curto: pode resolver assim que as apostas fecham
+    const resolveBy = new anchor.BN(now + 3600); // árbitro tem 1h pra resolver
 
     console.log("Criando partida...");
     await program.methods
-      .createMatch(
+      .createMatch({
         id,
         stakeLamports,
-        deadline,
-        playerA.publicKey,
-        playerB.publicKey
-      )
+        fundBy: betsCloseAt, // players têm até as apostas fecharem pra depositar
+        betsCloseAt,
+        earliestResolution,
+        resolveBy,
+        playerA: playerA.publicKey,
+        playerB: playerB.publicKey,
+        unmatchedPoolPolicy: { refundBettors: {} },
+        arbiterFeeBps: 0,
+      })
       .accounts({
         arbiter,
+        config: configPda,
+        matchCounter: counterPda,
         matchAccount: matchPda,
+        vault: vaultPda,
         systemProgram: SystemProgram.programId,
+        ...eventAccounts,
       })
       .rpc();
 
@@ -75,8 +145,11 @@ describe("snake-betting end-to-end", () => {
       .joinAsPlayer()
       .accounts({
         player: playerA.publicKey,
+        config: configPda,
         matchAccount: matchPda,
+        vault: vaultPda,
         systemProgram: SystemProgram.programId,
+        ...eventAccounts,
       })
       .signers([playerA])
       .rpc();
@@ -86,8 +159,11 @@ describe("snake-betting end-to-end", () => {
       .joinAsPlayer()
       .accounts({
         player: playerB.publicKey,
+        config: configPda,
         matchAccount: matchPda,
+        vault: vaultPda,
         systemProgram: SystemProgram.programId,
+        ...eventAccounts,
       })
       .signers([playerB])
       .rpc();
@@ -113,9 +189,12 @@ describe("snake-betting end-to-end", () => {
       .placeBet({ playerA: {} }, betAmountA) // Side::PlayerA
       .accounts({
         bettor: bettorA.publicKey,
+        config: configPda,
         matchAccount: matchPda,
+        vault: vaultPda,
         participant: participantPdaA,
         systemProgram: SystemProgram.programId,
+        ...eventAccounts,
       })
       .signers([bettorA])
       .rpc();
@@ -125,9 +204,12 @@ describe("snake-betting end-to-end", () => {
       .placeBet({ playerB: {} }, betAmountB) // Side::PlayerB
       .accounts({
         bettor: bettorB.publicKey,
+        config: configPda,
         matchAccount: matchPda,
+        vault: vaultPda,
         participant: participantPdaB,
         systemProgram: SystemProgram.programId,
+        ...eventAccounts,
       })
       .signers([bettorB])
       .rpc();
@@ -136,8 +218,22 @@ describe("snake-betting end-to-end", () => {
     console.log("Total apostado lado A:", matchAccount.totalSideA.toString());
     console.log("Total apostado lado B:", matchAccount.totalSideB.toString());
 
-    // 6) Esperar passar o deadline (pra devnet talvez não precise, mas garantimos)
-    console.log("Esperando passar o deadline...");
+    // 6) Árbitro inicia o jogo (fecha as apostas)
+    console.log("Árbitro iniciando a partida...");
+    await program.methods
+      .startMatch()
+      .accounts({
+        arbiter,
+        matchAccount: matchPda,
+        ...eventAccounts,
+      })
+      .rpc();
+
+    matchAccount = await program.account.match.fetch(matchPda);
+    console.log("Status após start_match:", matchAccount.status); // esperado: InProgress
+
+    // Esperar chegar o earliest_resolution (pra devnet talvez não precise, mas garantimos)
+    console.log("Esperando o earliest_resolution...");
     await new Promise((resolve) => setTimeout(resolve, 65_000));
 
     // 7) Árbitro declara vencedor (vamos supor que Player A ganhou)
@@ -146,7 +242,12 @@ describe("snake-betting end-to-end", () => {
       .declareWinner({ playerA: {} }) // Side::PlayerA
       .accounts({
         arbiter,
+        config: configPda,
+        treasury: arbiter, // tesouraria configurada no initialize acima
         matchAccount: matchPda,
+        vault: vaultPda,
+        systemProgram: SystemProgram.programId,
+        ...eventAccounts,
       })
       .rpc();
 
@@ -161,7 +262,11 @@ describe("snake-betting end-to-end", () => {
       .withdrawWinnerStake()
       .accounts({
         winner: playerA.publicKey,
+        config: configPda,
         matchAccount: matchPda,
+        vault: vaultPda,
+        systemProgram: SystemProgram.programId,
+        ...eventAccounts,
       })
       .signers([playerA])
       .rpc();
@@ -181,8 +286,12 @@ describe("snake-betting end-to-end", () => {
       .claimBetPayout()
       .accounts({
         bettor: bettorA.publicKey,
+        config: configPda,
         matchAccount: matchPda,
+        vault: vaultPda,
         participant: participantPdaA,
+        systemProgram: SystemProgram.programId,
+        ...eventAccounts,
       })
       .signers([bettorA])
       .rpc();